@@ -45,4 +45,10 @@ Goodbye!";
         let tree = case.tree();
         println!("{tree}");
     }
+
+    let pred = predicates::str::diff(expected).side_by_side(60).context(1);
+    if let Some(case) = pred.find_case(false, actual) {
+        let tree = case.tree();
+        println!("{tree}");
+    }
 }
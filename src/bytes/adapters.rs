@@ -0,0 +1,406 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use crate::Predicate;
+#[cfg(feature = "normalize-line-endings")]
+use crate::bytes::NormalizedPredicate;
+use crate::reflection;
+use crate::str::encoding::DecodeError;
+use crate::utils;
+
+fn decode_base64(
+    text: &str,
+    url_safe: bool,
+    ignore_whitespace: bool,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::with_capacity(text.len() / 4 * 3);
+    let (mut acc, mut bits) = (0_u32, 0);
+    let (mut digits, mut padding) = (0_usize, 0_usize);
+    for (i, c) in text.char_indices() {
+        if ignore_whitespace && c.is_ascii_whitespace() {
+            continue;
+        }
+        if c == '=' {
+            padding += 1;
+            if 2 < padding {
+                return Err(DecodeError {
+                    offset: i,
+                    reason: "too much base64 padding".to_owned(),
+                });
+            }
+            continue;
+        }
+        let value = match c {
+            _ if 0 < padding => None,
+            'A'..='Z' => Some(u32::from(c) - u32::from('A')),
+            'a'..='z' => Some(u32::from(c) - u32::from('a') + 26),
+            '0'..='9' => Some(u32::from(c) - u32::from('0') + 52),
+            '+' if !url_safe => Some(62),
+            '/' if !url_safe => Some(63),
+            '-' if url_safe => Some(62),
+            '_' if url_safe => Some(63),
+            _ => None,
+        };
+        let Some(value) = value else {
+            return Err(DecodeError {
+                offset: i,
+                reason: format!("invalid base64 character {c:?}"),
+            });
+        };
+        acc = (acc << 6) | value;
+        bits += 6;
+        digits += 1;
+        if 8 <= bits {
+            bits -= 8;
+            decoded.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if digits % 4 == 1 || (0 < padding && (digits + padding) % 4 != 0) {
+        return Err(DecodeError {
+            offset: text.len(),
+            reason: "incomplete base64 data".to_owned(),
+        });
+    }
+    Ok(decoded)
+}
+
+fn decode_hex(text: &str, ignore_whitespace: bool) -> Result<Vec<u8>, DecodeError> {
+    let mut decoded = Vec::with_capacity(text.len() / 2);
+    let mut high = None;
+    for (i, c) in text.char_indices() {
+        if ignore_whitespace && c.is_ascii_whitespace() {
+            continue;
+        }
+        let Some(value) = c.to_digit(16) else {
+            return Err(DecodeError {
+                offset: i,
+                reason: format!("invalid hex character {c:?}"),
+            });
+        };
+        match high.take() {
+            Some(high) => decoded.push((high << 4 | value) as u8),
+            None => high = Some(value),
+        }
+    }
+    if high.is_some() {
+        return Err(DecodeError {
+            offset: text.len(),
+            reason: "incomplete hex byte".to_owned(),
+        });
+    }
+    Ok(decoded)
+}
+
+fn find_decoded_case<'a, P>(
+    pred: &'a dyn reflection::PredicateReflection,
+    p: &'a P,
+    expected: bool,
+    decoded: Result<Vec<u8>, DecodeError>,
+) -> Option<reflection::Case<'a>>
+where
+    P: Predicate<[u8]>,
+{
+    match (expected, decoded) {
+        (_, Ok(decoded)) => p.find_case(expected, &decoded).map(|child| {
+            child.add_product(reflection::Product::new(
+                "var as bytes",
+                utils::BytesAdapter(decoded),
+            ))
+        }),
+        (true, Err(_)) => None,
+        (false, Err(err)) => Some(
+            reflection::Case::new(Some(pred), false)
+                .add_product(reflection::Product::new("error", err)),
+        ),
+    }
+}
+
+/// Predicate adapter that decodes base64 for a `[u8]` predicate.
+///
+/// This is created by `pred.from_base64()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Predicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    p: P,
+    url_safe: bool,
+    ignore_whitespace: bool,
+}
+
+impl<P> Base64Predicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    /// Decode the URL-safe alphabet, which uses `-` and `_` in place of `+` and `/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::eq(b"\xFB\xFF").from_base64().url_safe();
+    /// assert_eq!(true, predicate_fn.eval("-_8"));
+    /// assert_eq!(false, predicate_fn.eval("+/8"));
+    /// ```
+    pub fn url_safe(mut self) -> Self {
+        self.url_safe = true;
+        self
+    }
+
+    /// Skip ASCII whitespace, like the line breaks of MIME-encoded data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::eq(b"Hello World").from_base64().ignore_whitespace();
+    /// assert_eq!(true, predicate_fn.eval("SGVsbG8g\nV29ybGQ=\n"));
+    /// ```
+    pub fn ignore_whitespace(mut self) -> Self {
+        self.ignore_whitespace = true;
+        self
+    }
+
+    fn decode(&self, variable: &str) -> Result<Vec<u8>, DecodeError> {
+        decode_base64(variable, self.url_safe, self.ignore_whitespace)
+    }
+}
+
+impl<P> Predicate<str> for Base64Predicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn eval(&self, variable: &str) -> bool {
+        self.decode(variable)
+            .map(|decoded| self.p.eval(&decoded))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        find_decoded_case(self, &self.p, expected, self.decode(variable))
+    }
+}
+
+impl<P> reflection::PredicateReflection for Base64Predicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![
+            reflection::Parameter::new("url safe", &self.url_safe),
+            reflection::Parameter::new("ignore whitespace", &self.ignore_whitespace),
+        ];
+        Box::new(params.into_iter())
+    }
+
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for Base64Predicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
+/// Predicate adapter that decodes hex for a `[u8]` predicate.
+///
+/// This is created by `pred.from_hex()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HexPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    p: P,
+    ignore_whitespace: bool,
+}
+
+impl<P> HexPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    /// Skip ASCII whitespace, like the spaces between bytes of a hexdump.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::eq(b"\xDE\xAD\xBE\xEF").from_hex().ignore_whitespace();
+    /// assert_eq!(true, predicate_fn.eval("de ad be ef"));
+    /// ```
+    pub fn ignore_whitespace(mut self) -> Self {
+        self.ignore_whitespace = true;
+        self
+    }
+
+    fn decode(&self, variable: &str) -> Result<Vec<u8>, DecodeError> {
+        decode_hex(variable, self.ignore_whitespace)
+    }
+}
+
+impl<P> Predicate<str> for HexPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn eval(&self, variable: &str) -> bool {
+        self.decode(variable)
+            .map(|decoded| self.p.eval(&decoded))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        find_decoded_case(self, &self.p, expected, self.decode(variable))
+    }
+}
+
+impl<P> reflection::PredicateReflection for HexPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![reflection::Parameter::new(
+            "ignore whitespace",
+            &self.ignore_whitespace,
+        )];
+        Box::new(params.into_iter())
+    }
+
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for HexPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
+/// `Predicate` extension adapting a `[u8]` Predicate.
+pub trait PredicateBytesExt
+where
+    Self: Predicate<[u8]>,
+    Self: Sized,
+{
+    /// Returns a `Base64Predicate` that adapts `Self` to a base64-encoded `str` `Predicate`.
+    ///
+    /// Padding is optional.  See [`Base64Predicate`] for other alphabets and whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::starts_with(b"\x89PNG").from_base64();
+    /// assert_eq!(true, predicate_fn.eval("iVBORw0KGgo="));
+    /// assert_eq!(true, predicate_fn.eval("iVBORw0KGgo"));
+    /// assert_eq!(false, predicate_fn.eval("R0lGODlh"));
+    /// assert_eq!(false, predicate_fn.eval("not base64!"));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base64(self) -> Base64Predicate<Self> {
+        Base64Predicate {
+            p: self,
+            url_safe: false,
+            ignore_whitespace: false,
+        }
+    }
+
+    /// Returns a `HexPredicate` that adapts `Self` to a hex-encoded `str` `Predicate`.
+    ///
+    /// Both upper and lower case digits are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::eq(b"\xCA\xFE").from_hex();
+    /// assert_eq!(true, predicate_fn.eval("cafe"));
+    /// assert_eq!(true, predicate_fn.eval("CAFE"));
+    /// assert_eq!(false, predicate_fn.eval("caf"));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_hex(self) -> HexPredicate<Self> {
+        HexPredicate {
+            p: self,
+            ignore_whitespace: false,
+        }
+    }
+
+    /// Returns a `NormalizedPredicate` that normalizes `\r\n` and `\r` line endings in the data
+    /// passed to `Self` to `\n`.
+    ///
+    /// Only the variable is normalized; `Self` sees `\n` line endings and so should expect them.
+    /// To compare against a file with either kind of line ending, see
+    /// [`BinaryFilePredicate::normalize_newlines`][crate::path::BinaryFilePredicate::normalize_newlines].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::eq(b"one\ntwo\n").normalize_newlines();
+    /// let variable: &[u8] = b"one\r\ntwo\r\n";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// let variable: &[u8] = b"one\r\n\r\ntwo";
+    /// assert_eq!(false, predicate_fn.eval(variable));
+    /// ```
+    #[cfg(feature = "normalize-line-endings")]
+    fn normalize_newlines(self) -> NormalizedPredicate<Self> {
+        NormalizedPredicate { p: self }
+    }
+}
+
+impl<P> PredicateBytesExt for P where P: Predicate<[u8]> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips() {
+        assert_eq!(decode_base64("", false, false).unwrap(), b"");
+        assert_eq!(decode_base64("Zg==", false, false).unwrap(), b"f");
+        assert_eq!(decode_base64("Zm8=", false, false).unwrap(), b"fo");
+        assert_eq!(decode_base64("Zm9v", false, false).unwrap(), b"foo");
+        assert_eq!(decode_base64("Zm9vYg", false, false).unwrap(), b"foob");
+    }
+
+    #[test]
+    fn base64_errors() {
+        let err = |text| decode_base64(text, false, false).unwrap_err().to_string();
+        assert_eq!(err("Zm9v-A"), "invalid base64 character '-' from index 4");
+        assert_eq!(err("Zg=A"), "invalid base64 character 'A' from index 3");
+        assert_eq!(err("Zg==="), "too much base64 padding from index 4");
+        assert_eq!(err("Zm9vY"), "incomplete base64 data from index 5");
+        assert_eq!(err("Zm8=="), "incomplete base64 data from index 5");
+    }
+
+    #[test]
+    fn hex_errors() {
+        let err = |text| decode_hex(text, false).unwrap_err().to_string();
+        assert_eq!(err("abz"), "invalid hex character 'z' from index 2");
+        assert_eq!(err("abc"), "incomplete hex byte from index 3");
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use crate::Predicate;
+use crate::hexdump;
+use crate::reflection;
+use crate::utils;
+
+/// Offsets of the non-overlapping occurrences of `pattern` within `variable`.
+fn match_indices<'s>(variable: &'s [u8], pattern: &'s [u8]) -> impl Iterator<Item = usize> + 's {
+    let mut start = 0;
+    std::iter::from_fn(move || {
+        if variable.len() < start {
+            return None;
+        }
+        let offset = variable[start..]
+            .windows(pattern.len().max(1))
+            .position(|window| window.starts_with(pattern))
+            .or_else(|| (pattern.is_empty() && start == variable.len()).then_some(0))?;
+        let index = start + offset;
+        start = index + pattern.len().max(1);
+        Some(index)
+    })
+}
+
+/// Predicate that checks for empty bytes.
+///
+/// This is created by `predicates::bytes::is_empty`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsEmptyPredicate {}
+
+impl Predicate<[u8]> for IsEmptyPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        variable.is_empty()
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for IsEmptyPredicate {}
+
+impl fmt::Display for IsEmptyPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("is_empty"),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures bytes are empty
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::is_empty();
+/// assert_eq!(true, predicate_fn.eval(b""));
+/// assert_eq!(false, predicate_fn.eval(b"\xFF"));
+/// ```
+pub fn is_empty() -> IsEmptyPredicate {
+    IsEmptyPredicate {}
+}
+
+/// Predicate checks start of bytes
+///
+/// This is created by `predicates::bytes::starts_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartsWithPredicate {
+    pattern: utils::BytesAdapter,
+}
+
+impl Predicate<[u8]> for StartsWithPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        variable.starts_with(&self.pattern.0)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for StartsWithPredicate {}
+
+impl fmt::Display for StartsWithPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("starts_with"),
+            palette.expected(&self.pattern),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures bytes start with `pattern`
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::starts_with(b"\x7FELF");
+/// assert_eq!(true, predicate_fn.eval(b"\x7FELF\x02\x01"));
+/// assert_eq!(false, predicate_fn.eval(b"MZ\x90\x00"));
+/// ```
+pub fn starts_with<P>(pattern: P) -> StartsWithPredicate
+where
+    P: AsRef<[u8]>,
+{
+    StartsWithPredicate {
+        pattern: utils::BytesAdapter(pattern.as_ref().to_owned()),
+    }
+}
+
+/// Predicate checks end of bytes
+///
+/// This is created by `predicates::bytes::ends_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndsWithPredicate {
+    pattern: utils::BytesAdapter,
+}
+
+impl Predicate<[u8]> for EndsWithPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        variable.ends_with(&self.pattern.0)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for EndsWithPredicate {}
+
+impl fmt::Display for EndsWithPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("ends_with"),
+            palette.expected(&self.pattern),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures bytes end with `pattern`
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::ends_with(b"\r\n");
+/// assert_eq!(true, predicate_fn.eval(b"\xFFline\r\n"));
+/// assert_eq!(false, predicate_fn.eval(b"\xFFline\n"));
+/// ```
+pub fn ends_with<P>(pattern: P) -> EndsWithPredicate
+where
+    P: AsRef<[u8]>,
+{
+    EndsWithPredicate {
+        pattern: utils::BytesAdapter(pattern.as_ref().to_owned()),
+    }
+}
+
+/// Predicate that checks for patterns in bytes.
+///
+/// This is created by `predicates::bytes::contains`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainsPredicate {
+    pattern: utils::BytesAdapter,
+}
+
+impl ContainsPredicate {
+    /// Require a specific count of matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::contains(b"\x00\x00").count(2);
+    /// assert_eq!(true, predicate_fn.eval(b"\x01\x00\x00\x02\x00\x00"));
+    /// assert_eq!(false, predicate_fn.eval(b"\x01\x00\x00\x02"));
+    /// ```
+    pub fn count(self, count: usize) -> MatchesPredicate {
+        MatchesPredicate {
+            pattern: self.pattern,
+            count,
+        }
+    }
+}
+
+impl Predicate<[u8]> for ContainsPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        match_indices(variable, &self.pattern.0).next().is_some()
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for ContainsPredicate {}
+
+impl fmt::Display for ContainsPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("contains"),
+            palette.expected(&self.pattern),
+        )
+    }
+}
+
+/// Predicate that checks for repeated patterns in bytes.
+///
+/// This is created by `predicates::bytes::contains(...).count`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchesPredicate {
+    pattern: utils::BytesAdapter,
+    count: usize,
+}
+
+impl Predicate<[u8]> for MatchesPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        match_indices(variable, &self.pattern.0).count() == self.count
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        let offsets: Vec<_> = match_indices(variable, &self.pattern.0)
+            .map(|offset| format!("0x{offset:x}"))
+            .collect();
+        let result = self.count == offsets.len();
+        if result == expected {
+            let mut case = reflection::Case::new(Some(self), result)
+                .add_product(reflection::Product::new(
+                    "var",
+                    utils::BytesAdapter(variable.to_owned()),
+                ))
+                .add_product(reflection::Product::new("actual count", offsets.len()));
+            if !offsets.is_empty() {
+                case = case.add_product(reflection::Product::new("offsets", offsets.join(", ")));
+            }
+            Some(case)
+        } else {
+            None
+        }
+    }
+}
+
+impl reflection::PredicateReflection for MatchesPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![reflection::Parameter::new("count", &self.count)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for MatchesPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("contains"),
+            palette.expected(&self.pattern),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures bytes contain `pattern`
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::contains(b"\xDE\xAD");
+/// assert_eq!(true, predicate_fn.eval(b"\x00\xDE\xAD\xBE\xEF"));
+/// assert_eq!(false, predicate_fn.eval(b"\x00\xBE\xEF"));
+/// ```
+pub fn contains<P>(pattern: P) -> ContainsPredicate
+where
+    P: AsRef<[u8]>,
+{
+    ContainsPredicate {
+        pattern: utils::BytesAdapter(pattern.as_ref().to_owned()),
+    }
+}
+
+/// Predicate that checks bytes for equality, reporting a hexdump diff on failure.
+///
+/// This is created by `predicates::bytes::eq`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EqPredicate {
+    orig: utils::BytesAdapter,
+}
+
+impl Predicate<[u8]> for EqPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.orig.0 == variable
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let case = case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ));
+            hexdump::products(&self.orig.0, variable)
+                .into_iter()
+                .fold(case, |case, product| case.add_product(product))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for EqPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![reflection::Parameter::new("original", &self.orig)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for EqPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.var("var"),
+            palette.description("=="),
+            palette.expected("original"),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures bytes equal `orig`, reporting a hexdump diff on
+/// failure
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::eq(b"\x00\x01\x02");
+/// assert_eq!(true, predicate_fn.eval(b"\x00\x01\x02"));
+/// assert_eq!(false, predicate_fn.eval(b"\x00\x01\x03"));
+/// ```
+pub fn eq<P>(orig: P) -> EqPredicate
+where
+    P: AsRef<[u8]>,
+{
+    EqPredicate {
+        orig: utils::BytesAdapter(orig.as_ref().to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn non_overlapping_matches() {
+        let offsets = |variable: &[u8], pattern: &[u8]| -> Vec<usize> {
+            match_indices(variable, pattern).collect()
+        };
+        assert_eq!(offsets(b"aaaa", b"aa"), [0, 2]);
+        assert_eq!(offsets(b"abcab", b"ab"), [0, 3]);
+        assert_eq!(offsets(b"ab", b"abc"), [] as [usize; 0]);
+        assert_eq!(offsets(b"ab", b""), [0, 1, 2]);
+        assert_eq!(offsets(b"", b""), [0]);
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use crate::Predicate;
+use crate::reflection;
+
+/// Replace `\r\n` and `\r` line endings with `\n`, leaving all other bytes untouched.
+pub(crate) fn normalized(variable: &[u8]) -> Vec<u8> {
+    // Each byte maps to the char with the same value, so the round trip is lossless.
+    normalize_line_endings::normalized(variable.iter().map(|&b| char::from(b)))
+        .map(|c| c as u8)
+        .collect()
+}
+
+/// Predicate adapter that normalizes the newlines contained in the variable being tested.
+///
+/// This is created by `pred.normalize_newlines()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    pub(crate) p: P,
+}
+
+impl<P> reflection::PredicateReflection for NormalizedPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> Predicate<[u8]> for NormalizedPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.p.eval(&normalized(variable))
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        self.p.find_case(expected, &normalized(variable))
+    }
+}
+
+impl<P> fmt::Display for NormalizedPredicate<P>
+where
+    P: Predicate<[u8]>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalized_keeps_other_bytes() {
+        assert_eq!(normalized(b"a\r\nb\rc\n\xFF\r"), b"a\nb\nc\n\xFF\n");
+    }
+
+    #[test]
+    fn normalizes_variable_only() {
+        let p = NormalizedPredicate {
+            p: crate::bytes::eq(b"a\nb"),
+        };
+        assert!(p.eval(b"a\r\nb"));
+        assert!(p.eval(b"a\rb"));
+
+        let p = NormalizedPredicate {
+            p: crate::bytes::eq(b"a\r\nb"),
+        };
+        assert!(!p.eval(b"a\r\nb"));
+    }
+
+    #[test]
+    fn adapters_are_unambiguous() {
+        use crate::prelude::*;
+
+        // `BooleanPredicate` is both a `str` and a `[u8]` predicate.
+        let p = predicate::always().normalize();
+        assert!(p.eval("a\r\nb"));
+        let p = predicate::always().normalize_newlines();
+        assert!(p.eval(b"a\r\nb".as_slice()));
+    }
+}
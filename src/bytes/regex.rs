@@ -0,0 +1,204 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use crate::Predicate;
+use crate::reflection;
+use crate::str::RegexError;
+use crate::utils;
+
+/// Bytes shown on either side of a match.
+const CONTEXT_BYTES: usize = 16;
+/// Matches shown before the rest are summarized.
+const MAX_MATCHES: usize = 10;
+
+/// Describe each match of `re` with its offset and the surrounding bytes, decoded lossily.
+fn match_products(re: &regex::bytes::Regex, variable: &[u8]) -> Vec<reflection::Product> {
+    let mut products = Vec::new();
+    let mut matches = re.find_iter(variable);
+    for (i, m) in matches.by_ref().take(MAX_MATCHES).enumerate() {
+        let start = m.start().saturating_sub(CONTEXT_BYTES);
+        let end = (m.end() + CONTEXT_BYTES).min(variable.len());
+        let context = String::from_utf8_lossy(&variable[start..end]);
+        products.push(reflection::Product::new(
+            format!("match {}", i + 1),
+            format!("0x{:x}..0x{:x}: {context:?}", m.start(), m.end()),
+        ));
+    }
+    let remaining = matches.count();
+    if 0 < remaining {
+        products.push(reflection::Product::new("more matches", remaining));
+    }
+    products
+}
+
+/// Predicate that uses regex matching on bytes
+///
+/// This is created by the `predicate::bytes::is_match`.
+#[derive(Debug, Clone)]
+pub struct RegexPredicate {
+    re: regex::bytes::Regex,
+}
+
+impl RegexPredicate {
+    /// Require a specific count of matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::bytes::is_match("T[a-z]*").unwrap().count(3);
+    /// assert_eq!(true, predicate_fn.eval(b"One Two \xFF Three Two One"));
+    /// assert_eq!(false, predicate_fn.eval(b"One Two \xFF Three"));
+    /// ```
+    pub fn count(self, count: usize) -> RegexMatchesPredicate {
+        RegexMatchesPredicate { re: self.re, count }
+    }
+}
+
+impl Predicate<[u8]> for RegexPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.re.is_match(variable)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let case = case.add_product(reflection::Product::new(
+                "var",
+                utils::BytesAdapter(variable.to_owned()),
+            ));
+            match_products(&self.re, variable)
+                .into_iter()
+                .fold(case, |case, product| case.add_product(product))
+        })
+    }
+}
+
+impl reflection::PredicateReflection for RegexPredicate {}
+
+impl fmt::Display for RegexPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("is_match"),
+            palette.expected(&self.re),
+        )
+    }
+}
+
+/// Predicate that checks for repeated patterns in bytes.
+///
+/// This is created by `predicates::bytes::is_match(...).count`.
+#[derive(Debug, Clone)]
+pub struct RegexMatchesPredicate {
+    re: regex::bytes::Regex,
+    count: usize,
+}
+
+impl Predicate<[u8]> for RegexMatchesPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.re.find_iter(variable).count() == self.count
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        let actual_count = self.re.find_iter(variable).count();
+        let result = self.count == actual_count;
+        if result == expected {
+            let case = reflection::Case::new(Some(self), result)
+                .add_product(reflection::Product::new(
+                    "var",
+                    utils::BytesAdapter(variable.to_owned()),
+                ))
+                .add_product(reflection::Product::new("actual count", actual_count));
+            Some(
+                match_products(&self.re, variable)
+                    .into_iter()
+                    .fold(case, |case, product| case.add_product(product)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl reflection::PredicateReflection for RegexMatchesPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![reflection::Parameter::new("count", &self.count)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for RegexMatchesPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("is_match"),
+            palette.expected(&self.re),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that uses a regular expression to match bytes.
+///
+/// Unlike `predicate::str::is_match`, the variable doesn't need to be valid UTF-8.  Unicode
+/// classes still match UTF-8 encoded text; use `(?-u)` to match arbitrary bytes, like
+/// `(?-u)\xFF`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::bytes::is_match("^Hello").unwrap();
+/// assert_eq!(true, predicate_fn.eval(b"Hello \xFF World"));
+/// assert_eq!(false, predicate_fn.eval(b"Food World"));
+/// ```
+pub fn is_match<S>(pattern: S) -> Result<RegexPredicate, RegexError>
+where
+    S: AsRef<str>,
+{
+    regex::bytes::Regex::new(pattern.as_ref()).map(|re| RegexPredicate { re })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn match_context() {
+        let p = predicate::bytes::is_match("b+").unwrap().count(1);
+        let case = p.find_case(false, b"a\xFFbb\xFFcbd").unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect();
+        assert_eq!(
+            products,
+            [
+                ("var".to_owned(), r#"b"a\xffbb\xffcbd""#.to_owned()),
+                ("actual count".to_owned(), "2".to_owned()),
+                (
+                    "match 1".to_owned(),
+                    "0x2..0x4: \"a\u{fffd}bb\u{fffd}cbd\"".to_owned()
+                ),
+                (
+                    "match 2".to_owned(),
+                    "0x6..0x7: \"a\u{fffd}bb\u{fffd}cbd\"".to_owned()
+                ),
+            ]
+        );
+    }
+}
@@ -26,6 +26,13 @@ impl Palette {
         }
     }
 
+    /// Whether nothing is styled, so markup must be spelled out in text.
+    pub(crate) fn is_plain(self) -> bool {
+        self.description == anstyle::Style::new()
+            && self.var == anstyle::Style::new()
+            && self.expected == anstyle::Style::new()
+    }
+
     pub(crate) fn description<D: std::fmt::Display>(self, display: D) -> Styled<D> {
         Styled::new(display, self.description)
     }
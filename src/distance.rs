@@ -0,0 +1,286 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Edit distance between sequences.
+
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Edit {
+    Equal,
+    Delete,
+    Insert,
+    Substitute,
+}
+
+/// Compute a minimal edit script turning `a` into `b`.
+pub(crate) fn edit_script<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let width = b.len() + 1;
+    let mut table = vec![0; (a.len() + 1) * width];
+    for (i, row) in table.chunks_mut(width).enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in table[..width].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            table[i * width + j] = (table[(i - 1) * width + j - 1] + cost)
+                .min(table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1);
+        }
+    }
+
+    let mut edits = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (a.len(), b.len());
+    while 0 < i || 0 < j {
+        let current = table[i * width + j];
+        if 0 < i && 0 < j && a[i - 1] == b[j - 1] && current == table[(i - 1) * width + j - 1] {
+            edits.push(Edit::Equal);
+            i -= 1;
+            j -= 1;
+        } else if 0 < i && current == table[(i - 1) * width + j] + 1 {
+            edits.push(Edit::Delete);
+            i -= 1;
+        } else if 0 < j && current == table[i * width + j - 1] + 1 {
+            edits.push(Edit::Insert);
+            j -= 1;
+        } else {
+            edits.push(Edit::Substitute);
+            i -= 1;
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+/// Count the edits needed to turn `a` into `b`.
+///
+/// With `transpositions`, swapping two adjacent items counts as a single edit (the optimal string
+/// alignment variant of Damerau-Levenshtein distance).
+pub(crate) fn distance<T: PartialEq>(a: &[T], b: &[T], transpositions: bool) -> usize {
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j - 1] + cost)
+                .min(previous[j] + 1)
+                .min(current[j - 1] + 1);
+            if transpositions && 1 < i && 1 < j && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Find the run of `text` closest to `pattern` by edit distance.
+///
+/// With `anchored`, the run must start at the beginning of `text`.  Returns the run's range
+/// within `text` and its distance from `pattern`.
+pub(crate) fn closest_substring<T: PartialEq>(
+    pattern: &[T],
+    text: &[T],
+    anchored: bool,
+) -> (Range<usize>, usize) {
+    let width = text.len() + 1;
+    let mut table = vec![0; (pattern.len() + 1) * width];
+    for (i, row) in table.chunks_mut(width).enumerate() {
+        row[0] = i;
+    }
+    if anchored {
+        for (j, cell) in table[..width].iter_mut().enumerate() {
+            *cell = j;
+        }
+    }
+    for i in 1..=pattern.len() {
+        for j in 1..=text.len() {
+            let cost = usize::from(pattern[i - 1] != text[j - 1]);
+            table[i * width + j] = (table[(i - 1) * width + j - 1] + cost)
+                .min(table[(i - 1) * width + j] + 1)
+                .min(table[i * width + j - 1] + 1);
+        }
+    }
+
+    let last = &table[pattern.len() * width..];
+    let (end, distance) = last
+        .iter()
+        .copied()
+        .enumerate()
+        // Anchored runs of equal distance differ in length; prefer the one closest to `pattern`.
+        .min_by_key(|(j, distance)| {
+            (
+                *distance,
+                if anchored {
+                    j.abs_diff(pattern.len())
+                } else {
+                    0
+                },
+            )
+        })
+        .expect("table always has a column");
+    let (mut i, mut j) = (pattern.len(), end);
+    while 0 < i {
+        let current = table[i * width + j];
+        if 0 < j
+            && current
+                == table[(i - 1) * width + j - 1] + usize::from(pattern[i - 1] != text[j - 1])
+        {
+            i -= 1;
+            j -= 1;
+        } else if current == table[(i - 1) * width + j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    let start = if anchored { 0 } else { j };
+    (start..end, distance)
+}
+
+/// Render `added` inline, marking where it differs from `removed`.
+pub(crate) fn inline_diff(removed: &[&str], added: &[&str], palette: crate::Palette) -> String {
+    let mut line = String::new();
+    let mut removed_run = String::new();
+    let mut added_run = String::new();
+    let (mut i, mut j) = (0, 0);
+    for edit in edit_script(removed, added) {
+        match edit {
+            Edit::Equal => {
+                line.push_str(&mark_removed(&removed_run, palette));
+                line.push_str(&mark_added(&added_run, palette));
+                removed_run.clear();
+                added_run.clear();
+                line.push_str(added[j]);
+                i += 1;
+                j += 1;
+            }
+            Edit::Delete => {
+                removed_run.push_str(removed[i]);
+                i += 1;
+            }
+            Edit::Insert => {
+                added_run.push_str(added[j]);
+                j += 1;
+            }
+            Edit::Substitute => {
+                removed_run.push_str(removed[i]);
+                added_run.push_str(added[j]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    line.push_str(&mark_removed(&removed_run, palette));
+    line.push_str(&mark_added(&added_run, palette));
+    line
+}
+
+/// Render `removed` and `added` as separate lines, each marking where it differs from the other.
+#[cfg(feature = "diff")]
+pub(crate) fn paired_diff(
+    removed: &[&str],
+    added: &[&str],
+    palette: crate::Palette,
+) -> (String, String) {
+    let (mut removed_line, mut added_line) = (String::new(), String::new());
+    let mut removed_run = String::new();
+    let mut added_run = String::new();
+    let (mut i, mut j) = (0, 0);
+    for edit in edit_script(removed, added) {
+        match edit {
+            Edit::Equal => {
+                removed_line.push_str(&mark_removed(&removed_run, palette));
+                added_line.push_str(&mark_added(&added_run, palette));
+                removed_run.clear();
+                added_run.clear();
+                removed_line.push_str(removed[i]);
+                added_line.push_str(added[j]);
+                i += 1;
+                j += 1;
+            }
+            Edit::Delete => {
+                removed_run.push_str(removed[i]);
+                i += 1;
+            }
+            Edit::Insert => {
+                added_run.push_str(added[j]);
+                j += 1;
+            }
+            Edit::Substitute => {
+                removed_run.push_str(removed[i]);
+                added_run.push_str(added[j]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    removed_line.push_str(&mark_removed(&removed_run, palette));
+    added_line.push_str(&mark_added(&added_run, palette));
+    (removed_line, added_line)
+}
+
+pub(crate) fn mark_removed(text: &str, palette: crate::Palette) -> String {
+    if text.is_empty() {
+        String::new()
+    } else if palette.is_plain() {
+        format!("[-{text}-]")
+    } else {
+        format!("{:#}", palette.expected(text))
+    }
+}
+
+pub(crate) fn mark_added(text: &str, palette: crate::Palette) -> String {
+    if text.is_empty() {
+        String::new()
+    } else if palette.is_plain() {
+        format!("{{+{text}+}}")
+    } else {
+        format!("{:#}", palette.var(text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn distance_with_transpositions() {
+        assert_eq!(distance(&chars("kitten"), &chars("sitting"), false), 3);
+        assert_eq!(distance(&chars("abcd"), &chars("acbd"), false), 2);
+        assert_eq!(distance(&chars("abcd"), &chars("acbd"), true), 1);
+        assert_eq!(distance(&chars(""), &chars("abc"), true), 3);
+    }
+
+    #[test]
+    fn closest_substring_unanchored() {
+        let text = chars("   Compiling  foo v1");
+        let (range, distance) = closest_substring(&chars("Compiling foo"), &text, false);
+        assert_eq!(text[range].iter().collect::<String>(), "Compiling  foo");
+        assert_eq!(distance, 1);
+    }
+
+    #[test]
+    fn closest_substring_anchored() {
+        let text = chars("compiling foo v1");
+        let (range, distance) = closest_substring(&chars("Compiling foo"), &text, true);
+        assert_eq!(range, 0..13);
+        assert_eq!(distance, 1);
+    }
+}
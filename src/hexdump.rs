@@ -0,0 +1,184 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hexdump-style diffs of byte slices.
+
+use std::fmt::Write as _;
+
+use crate::reflection;
+
+const ROW_WIDTH: usize = 16;
+/// Unchanged rows shown around each differing row.
+const CONTEXT_ROWS: usize = 1;
+/// Differing rows shown before the rest are summarized.
+const MAX_DIFFERING_ROWS: usize = 32;
+
+/// Offset of the first byte that differs, including where one side runs out.
+pub(crate) fn first_difference(orig: &[u8], variable: &[u8]) -> Option<usize> {
+    orig.iter()
+        .zip(variable)
+        .position(|(o, v)| o != v)
+        .or_else(|| (orig.len() != variable.len()).then(|| orig.len().min(variable.len())))
+}
+
+/// Describe how `variable` differs from `orig`: where, by how much, and a hexdump diff.
+pub(crate) fn products(orig: &[u8], variable: &[u8]) -> Vec<reflection::Product> {
+    let Some(offset) = first_difference(orig, variable) else {
+        return Vec::new();
+    };
+    let mut products = vec![reflection::Product::new(
+        "first difference",
+        format!("0x{offset:08x}"),
+    )];
+    if orig.len() != variable.len() {
+        products.push(reflection::Product::new(
+            "length",
+            format!(
+                "orig is {} bytes, var is {} bytes",
+                orig.len(),
+                variable.len()
+            ),
+        ));
+    }
+    products.push(reflection::Product::new("diff", diff(orig, variable)));
+    products
+}
+
+/// Render the differing rows of a hexdump of `variable` against `orig`, suitable for a `diff`
+/// product.
+pub(crate) fn diff(orig: &[u8], variable: &[u8]) -> String {
+    render_diff(orig, variable, crate::Palette::new(true))
+}
+
+fn render_diff(orig: &[u8], variable: &[u8], palette: crate::Palette) -> String {
+    let rows = orig.len().max(variable.len()).div_ceil(ROW_WIDTH);
+    let differs: Vec<_> = (0..rows)
+        .map(|i| row(orig, i) != row(variable, i))
+        .collect();
+
+    let mut diff = format!(
+        "\n{:#} {:#}\n{:#} {:#}\n",
+        palette.expected("---"),
+        palette.expected("orig"),
+        palette.var("+++"),
+        palette.var("var"),
+    );
+    let mut shown = 0;
+    let mut last = None;
+    for i in 0..rows {
+        let context = i.saturating_sub(CONTEXT_ROWS)..=(i + CONTEXT_ROWS).min(rows - 1);
+        if !differs[context].contains(&true) {
+            continue;
+        }
+        if differs[i] {
+            if shown == MAX_DIFFERING_ROWS {
+                let remaining = differs[i..].iter().filter(|d| **d).count();
+                let _ = writeln!(
+                    diff,
+                    "{:#}",
+                    palette.description(format_args!("... {remaining} more differing rows"))
+                );
+                break;
+            }
+            shown += 1;
+        }
+        if last.map(|last| last + 1 < i).unwrap_or(false) {
+            let _ = writeln!(diff, "{:#}", palette.description("..."));
+        }
+
+        let offset = i * ROW_WIDTH;
+        if differs[i] {
+            let (o, v) = (row(orig, i), row(variable, i));
+            render_row(&mut diff, &palette.expected("-"), offset, o, v, |b| {
+                format!("{:#}", palette.expected(b))
+            });
+            render_row(&mut diff, &palette.var("+"), offset, v, o, |b| {
+                format!("{:#}", palette.var(b))
+            });
+        } else {
+            render_row(&mut diff, &" ", offset, row(orig, i), row(orig, i), |b| b);
+        }
+        last = Some(i);
+    }
+    diff
+}
+
+fn row(data: &[u8], i: usize) -> &[u8] {
+    let start = (i * ROW_WIDTH).min(data.len());
+    let end = (start + ROW_WIDTH).min(data.len());
+    &data[start..end]
+}
+
+/// Render `data` as offset, hex, and ASCII columns, highlighting the bytes that differ from
+/// `other`.
+fn render_row(
+    out: &mut String,
+    marker: &dyn std::fmt::Display,
+    offset: usize,
+    data: &[u8],
+    other: &[u8],
+    highlight: impl Fn(String) -> String,
+) {
+    let mut hex = String::new();
+    let mut ascii = String::new();
+    for i in 0..ROW_WIDTH {
+        if i == ROW_WIDTH / 2 {
+            hex.push(' ');
+        }
+        let Some(&b) = data.get(i) else {
+            hex.push_str("   ");
+            continue;
+        };
+        let c = if b.is_ascii_graphic() || b == b' ' {
+            char::from(b)
+        } else {
+            '.'
+        };
+        if other.get(i) == Some(&b) {
+            let _ = write!(hex, " {b:02x}");
+            ascii.push(c);
+        } else {
+            let _ = write!(hex, " {}", highlight(format!("{b:02x}")));
+            ascii.push_str(&highlight(c.to_string()));
+        }
+    }
+    let _ = writeln!(out, "{marker:#}{offset:08x} {hex}  |{ascii}|");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_difference_offsets() {
+        assert_eq!(first_difference(b"abc", b"abc"), None);
+        assert_eq!(first_difference(b"abc", b"abd"), Some(2));
+        assert_eq!(first_difference(b"abc", b"ab"), Some(2));
+        assert_eq!(first_difference(b"", b"a"), Some(0));
+    }
+
+    #[test]
+    fn only_differing_rows() {
+        let orig: Vec<u8> = (0..64).collect();
+        let mut variable = orig.clone();
+        variable[40] = b'A';
+        variable.truncate(50);
+        assert_eq!(
+            render_diff(&orig, &variable, crate::Palette::plain()),
+            "
+--- orig
++++ var
+ 00000010  10 11 12 13 14 15 16 17  18 19 1a 1b 1c 1d 1e 1f  |................|
+-00000020  20 21 22 23 24 25 26 27  28 29 2a 2b 2c 2d 2e 2f  | !\"#$%&'()*+,-./|
++00000020  20 21 22 23 24 25 26 27  41 29 2a 2b 2c 2d 2e 2f  | !\"#$%&'A)*+,-./|
+-00000030  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|
++00000030  30 31                                             |01|
+"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde_json::Value;
+
+use crate::Predicate;
+use crate::reflection;
+
+/// Predicate adapter that serializes an item for a JSON value `Predicate`.
+///
+/// This is created by `pred.from_serialize()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SerializePredicate<P, T>
+where
+    P: Predicate<Value>,
+    T: serde::Serialize + ?Sized,
+{
+    p: P,
+    _phantom: PhantomData<T>,
+}
+
+unsafe impl<P, T> Send for SerializePredicate<P, T>
+where
+    P: Predicate<Value> + Send,
+    T: serde::Serialize + ?Sized,
+{
+}
+
+unsafe impl<P, T> Sync for SerializePredicate<P, T>
+where
+    P: Predicate<Value> + Sync,
+    T: serde::Serialize + ?Sized,
+{
+}
+
+impl<P, T> Predicate<T> for SerializePredicate<P, T>
+where
+    P: Predicate<Value>,
+    T: serde::Serialize + ?Sized,
+{
+    fn eval(&self, variable: &T) -> bool {
+        serde_json::to_value(variable)
+            .map(|value| self.p.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &T) -> Option<reflection::Case<'a>> {
+        match (expected, serde_json::to_value(variable)) {
+            (_, Ok(value)) => self.p.find_case(expected, &value).map(|child| {
+                child.add_product(reflection::Product::new("var as value", value.to_string()))
+            }),
+            (true, Err(_)) => None,
+            (false, Err(err)) => Some(
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("error", err)),
+            ),
+        }
+    }
+}
+
+impl<P, T> reflection::PredicateReflection for SerializePredicate<P, T>
+where
+    P: Predicate<Value>,
+    T: serde::Serialize + ?Sized,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P, T> fmt::Display for SerializePredicate<P, T>
+where
+    P: Predicate<Value>,
+    T: serde::Serialize + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
+/// `Predicate` extension adapting a JSON value Predicate.
+pub trait PredicateValueExt
+where
+    Self: Predicate<Value>,
+    Self: Sized,
+{
+    /// Returns a `SerializePredicate` that adapts `Self` to a `Predicate` over a
+    /// `serde::Serialize` item of type `T`.
+    ///
+    /// The item is serialized into a JSON value tree, so the structural predicates can be used
+    /// with types that aren't `PartialEq` or readable with `Debug`.  Items that can't be
+    /// represented as JSON, like maps with non-string keys, fail the predicate.
+    ///
+    /// A `str` is serialized as a JSON string, not parsed; use `Self` directly to check JSON
+    /// text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     ports: Vec<u16>,
+    /// }
+    ///
+    /// let config = Config {
+    ///     name: "web".to_owned(),
+    ///     ports: vec![80, 443],
+    /// };
+    ///
+    /// let predicate_fn =
+    ///     predicate::json::is_subset_of(json!({"name": "web"})).from_serialize::<Config>();
+    /// assert_eq!(true, predicate_fn.eval(&config));
+    ///
+    /// let predicate_fn = predicate::json::pointer("/ports/1", predicate::eq(json!(443)))
+    ///     .from_serialize::<Config>();
+    /// assert_eq!(true, predicate_fn.eval(&config));
+    ///
+    /// let predicate_fn =
+    ///     predicate::json::eq(json!({"name": "web", "ports": [80]})).from_serialize::<Config>();
+    /// assert_eq!(false, predicate_fn.eval(&config));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_serialize<T>(self) -> SerializePredicate<Self, T>
+    where
+        T: serde::Serialize + ?Sized,
+    {
+        SerializePredicate {
+            p: self,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P> PredicateValueExt for P where P: Predicate<Value> {}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn serialize_error_is_reported() {
+        let p = crate::json::eq(json!({})).from_serialize::<BTreeMap<(u8, u8), u8>>();
+        let variable: BTreeMap<(u8, u8), u8> = [((1, 2), 3)].into_iter().collect();
+        assert!(!p.eval(&variable));
+        let case = p.find_case(false, &variable).unwrap();
+        assert_eq!(case.products().next().unwrap().name(), "error");
+    }
+
+    #[test]
+    fn diff_includes_serialized_value() {
+        let p = crate::json::eq(json!([1, 2])).from_serialize::<[u8]>();
+        let case = p.find_case(false, &[1, 3][..]).unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| format!("{}: {}", product.name(), product.value()))
+            .collect();
+        assert_eq!(products, ["/1: expected 2, found 3", "var as value: [1,3]"]);
+    }
+
+    #[test]
+    fn str_is_serialized_not_parsed() {
+        let p = crate::json::eq(json!({"a": 1})).from_serialize::<str>();
+        assert!(!p.eval("{\"a\":1}"));
+        let p = crate::json::eq(json!("{\"a\":1}")).from_serialize::<str>();
+        assert!(p.eval("{\"a\":1}"));
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::Predicate;
+use crate::json::diff;
+use crate::json::format::Format;
+use crate::reflection;
+
+/// Find the case for a document that may have failed to parse.
+fn find_parsed_case<'a>(
+    predicate: &'a dyn reflection::PredicateReflection,
+    expected: bool,
+    parsed: Result<Value, String>,
+    find_case: impl FnOnce(&Value) -> Option<reflection::Case<'a>>,
+) -> Option<reflection::Case<'a>> {
+    match parsed {
+        Ok(value) => find_case(&value),
+        Err(err) => (!expected).then(|| {
+            reflection::Case::new(Some(predicate), false)
+                .add_product(reflection::Product::new("error", err))
+        }),
+    }
+}
+
+/// Find the case for the structural differences between two documents.
+fn find_diff_case<'a>(
+    predicate: &'a dyn reflection::PredicateReflection,
+    expected: bool,
+    differences: &[diff::Difference],
+    format: Format,
+) -> Option<reflection::Case<'a>> {
+    let result = differences.is_empty();
+    if result != expected {
+        return None;
+    }
+    Some(diff::products(differences, format).into_iter().fold(
+        reflection::Case::new(Some(predicate), result),
+        |case, product| case.add_product(product),
+    ))
+}
+
+/// Predicate that checks JSON documents are structurally equal.
+///
+/// This is created by `predicates::json::eq`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqPredicate {
+    pub(crate) expected: Value,
+    pub(crate) format: Format,
+}
+
+impl EqPredicate {
+    fn find_value_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &Value,
+    ) -> Option<reflection::Case<'a>> {
+        find_diff_case(
+            self,
+            expected,
+            &diff::diff(&self.expected, variable, false),
+            self.format,
+        )
+    }
+}
+
+impl Predicate<Value> for EqPredicate {
+    fn eval(&self, variable: &Value) -> bool {
+        diff::diff(&self.expected, variable, false).is_empty()
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &Value) -> Option<reflection::Case<'a>> {
+        self.find_value_case(expected, variable)
+    }
+}
+
+impl Predicate<str> for EqPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.format
+            .parse_str(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_str(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl Predicate<[u8]> for EqPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.format
+            .parse_bytes(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_bytes(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl reflection::PredicateReflection for EqPredicate {}
+
+impl fmt::Display for EqPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.var("var"),
+            palette.description("=="),
+            palette.expected(&self.expected),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures a JSON document is structurally equal to `expected`.
+///
+/// Object keys may be in any order, formatting is ignored, and numbers are compared by value.
+/// On failure, each differing, missing, or extra node is reported by its JSON pointer.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+/// use serde_json::json;
+///
+/// let predicate_fn = predicate::json::eq(json!({"name": "foo", "tags": [1, 2]}));
+/// assert_eq!(true, predicate_fn.eval(r#"{ "tags": [1, 2.0], "name": "foo" }"#));
+/// assert_eq!(false, predicate_fn.eval(r#"{"name": "foo", "tags": [2, 1]}"#));
+/// assert_eq!(false, predicate_fn.eval("not json"));
+/// ```
+pub fn eq<V>(expected: V) -> EqPredicate
+where
+    V: Into<Value>,
+{
+    EqPredicate {
+        expected: expected.into(),
+        format: Format::Json,
+    }
+}
+
+/// Predicate that checks a JSON document contains another.
+///
+/// This is created by `predicates::json::is_subset_of`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsetPredicate {
+    pub(crate) expected: Value,
+    pub(crate) format: Format,
+}
+
+impl SubsetPredicate {
+    fn find_value_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &Value,
+    ) -> Option<reflection::Case<'a>> {
+        find_diff_case(
+            self,
+            expected,
+            &diff::diff(&self.expected, variable, true),
+            self.format,
+        )
+    }
+}
+
+impl Predicate<Value> for SubsetPredicate {
+    fn eval(&self, variable: &Value) -> bool {
+        diff::diff(&self.expected, variable, true).is_empty()
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &Value) -> Option<reflection::Case<'a>> {
+        self.find_value_case(expected, variable)
+    }
+}
+
+impl Predicate<str> for SubsetPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.format
+            .parse_str(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_str(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl Predicate<[u8]> for SubsetPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.format
+            .parse_bytes(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_bytes(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl reflection::PredicateReflection for SubsetPredicate {}
+
+impl fmt::Display for SubsetPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.expected(&self.expected),
+            palette.description("is_subset_of"),
+            palette.var("var"),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures `expected` is a subset of a JSON document.
+///
+/// The document may have object keys and trailing array items that `expected` lacks; everything
+/// in `expected` must be present and equal, as with [`eq`].
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+/// use serde_json::json;
+///
+/// let predicate_fn = predicate::json::is_subset_of(json!({"status": "ok"}));
+/// assert_eq!(true, predicate_fn.eval(r#"{"status": "ok", "elapsed": 12}"#));
+/// assert_eq!(false, predicate_fn.eval(r#"{"status": "failed"}"#));
+/// ```
+pub fn is_subset_of<V>(expected: V) -> SubsetPredicate
+where
+    V: Into<Value>,
+{
+    SubsetPredicate {
+        expected: expected.into(),
+        format: Format::Json,
+    }
+}
+
+/// Predicate that checks the node at a JSON pointer.
+///
+/// This is created by `predicates::json::pointer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    pub(crate) path: String,
+    pub(crate) pointer: String,
+    pub(crate) p: P,
+    pub(crate) format: Format,
+}
+
+impl<P> PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn find_value_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &Value,
+    ) -> Option<reflection::Case<'a>> {
+        match variable.pointer(&self.pointer) {
+            Some(value) => self.p.find_case(expected, value).map(|child| {
+                reflection::Case::new(Some(self), child.result())
+                    .add_product(reflection::Product::new("value", value.to_string()))
+                    .add_child(child)
+            }),
+            None => (!expected).then(|| {
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("missing", self.path.clone()))
+            }),
+        }
+    }
+}
+
+impl<P> Predicate<Value> for PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn eval(&self, variable: &Value) -> bool {
+        variable
+            .pointer(&self.pointer)
+            .map(|value| self.p.eval(value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &Value) -> Option<reflection::Case<'a>> {
+        self.find_value_case(expected, variable)
+    }
+}
+
+impl<P> Predicate<str> for PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn eval(&self, variable: &str) -> bool {
+        self.format
+            .parse_str(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_str(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl<P> Predicate<[u8]> for PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.format
+            .parse_bytes(variable)
+            .map(|value| self.eval(&value))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        find_parsed_case(self, expected, self.format.parse_bytes(variable), |value| {
+            self.find_value_case(expected, value)
+        })
+    }
+}
+
+impl<P> reflection::PredicateReflection for PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for PointerPredicate<P>
+where
+    P: Predicate<Value>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({:?})",
+            palette.var("var"),
+            palette.description(self.format.path_name()),
+            self.path,
+        )
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the node at `pointer` within a JSON document.
+///
+/// `pointer` is a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901), like `"/a/b/0"`.  The
+/// predicate fails when there is no node at `pointer`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+/// use serde_json::json;
+///
+/// let predicate_fn = predicate::json::pointer("/items/0/id", predicate::eq(json!(7)));
+/// assert_eq!(true, predicate_fn.eval(r#"{"items": [{"id": 7}]}"#));
+/// assert_eq!(false, predicate_fn.eval(r#"{"items": [{"id": 8}]}"#));
+/// assert_eq!(false, predicate_fn.eval(r#"{"items": []}"#));
+/// ```
+pub fn pointer<S, P>(pointer: S, p: P) -> PointerPredicate<P>
+where
+    S: Into<String>,
+    P: Predicate<Value>,
+{
+    let pointer = pointer.into();
+    PointerPredicate {
+        path: pointer.clone(),
+        pointer,
+        p,
+        format: Format::Json,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn eq_reports_pointers() {
+        let p = eq(json!({"a": [1, 2], "b": "x"}));
+        let case = p.find_case(false, r#"{"a": [1, 3], "c": null}"#).unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| format!("{}: {}", product.name(), product.value()))
+            .collect();
+        assert_eq!(
+            products,
+            [
+                "/a/1: expected 2, found 3",
+                "/b: missing, expected \"x\"",
+                "/c: unexpected null",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_error_is_reported() {
+        let p = eq(json!(null));
+        let case = p.find_case(false, "{\n  \"a\": }").unwrap();
+        let error = case.products().next().unwrap();
+        assert_eq!(error.name(), "error");
+        assert!(error.value().to_string().contains("line 2 column 8"));
+    }
+
+    #[test]
+    fn pointer_missing_node() {
+        let p = pointer("/a/b", eq(json!(1)));
+        assert!(p.eval(&json!({"a": {"b": 1.0}})));
+        let case = p.find_case(false, &json!({"a": {}})).unwrap();
+        assert_eq!(case.products().next().unwrap().name(), "missing");
+    }
+
+    #[test]
+    fn eq_large_integers() {
+        assert!(!eq(json!(9007199254740993u64)).eval("9007199254740992"));
+        assert!(eq(json!(9007199254740993u64)).eval("9007199254740993"));
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Structural differences between JSON values.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::json::format::Format;
+use crate::reflection;
+
+/// Differences listed before the rest are summarized.
+const MAX_DIFFERENCES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Difference {
+    Changed {
+        pointer: String,
+        expected: Value,
+        actual: Value,
+    },
+    Missing {
+        pointer: String,
+        expected: Value,
+    },
+    Extra {
+        pointer: String,
+        actual: Value,
+    },
+}
+
+impl Difference {
+    fn pointer(&self) -> &str {
+        match self {
+            Difference::Changed { pointer, .. }
+            | Difference::Missing { pointer, .. }
+            | Difference::Extra { pointer, .. } => pointer,
+        }
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Changed {
+                expected, actual, ..
+            } => write!(f, "expected {expected}, found {actual}"),
+            Difference::Missing { expected, .. } => write!(f, "missing, expected {expected}"),
+            Difference::Extra { actual, .. } => write!(f, "unexpected {actual}"),
+        }
+    }
+}
+
+/// Compare `actual` to `expected`, listing each differing node by its JSON pointer.
+///
+/// With `subset`, `actual` may have object keys and trailing array items that `expected` lacks.
+pub(crate) fn diff(expected: &Value, actual: &Value, subset: bool) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    diff_at(
+        &mut String::new(),
+        expected,
+        actual,
+        subset,
+        &mut differences,
+    );
+    differences
+}
+
+fn diff_at(
+    pointer: &mut String,
+    expected: &Value,
+    actual: &Value,
+    subset: bool,
+    differences: &mut Vec<Difference>,
+) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected) in expected {
+                let len = pointer.len();
+                push_token(pointer, key);
+                match actual.get(key) {
+                    Some(actual) => diff_at(pointer, expected, actual, subset, differences),
+                    None => differences.push(Difference::Missing {
+                        pointer: pointer.clone(),
+                        expected: expected.clone(),
+                    }),
+                }
+                pointer.truncate(len);
+            }
+            if !subset {
+                for (key, actual) in actual {
+                    if !expected.contains_key(key) {
+                        let len = pointer.len();
+                        push_token(pointer, key);
+                        differences.push(Difference::Extra {
+                            pointer: pointer.clone(),
+                            actual: actual.clone(),
+                        });
+                        pointer.truncate(len);
+                    }
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for i in 0..expected.len().max(actual.len()) {
+                let len = pointer.len();
+                push_token(pointer, &i.to_string());
+                match (expected.get(i), actual.get(i)) {
+                    (Some(expected), Some(actual)) => {
+                        diff_at(pointer, expected, actual, subset, differences);
+                    }
+                    (Some(expected), None) => differences.push(Difference::Missing {
+                        pointer: pointer.clone(),
+                        expected: expected.clone(),
+                    }),
+                    (None, Some(actual)) if !subset => differences.push(Difference::Extra {
+                        pointer: pointer.clone(),
+                        actual: actual.clone(),
+                    }),
+                    (None, _) => {}
+                }
+                pointer.truncate(len);
+            }
+        }
+        // Integers compare exactly; only a float on either side widens the comparison to `f64`.
+        (Value::Number(e), Value::Number(a))
+            if e == a || ((e.is_f64() || a.is_f64()) && e.as_f64() == a.as_f64()) => {}
+        _ if expected == actual => {}
+        _ => differences.push(Difference::Changed {
+            pointer: pointer.clone(),
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+    }
+}
+
+/// Append `key` to `pointer`, escaped per RFC 6901.
+fn push_token(pointer: &mut String, key: &str) {
+    pointer.push('/');
+    pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
+
+/// Describe each difference as a product named by its path.
+pub(crate) fn products(differences: &[Difference], format: Format) -> Vec<reflection::Product> {
+    let mut products: Vec<_> = differences
+        .iter()
+        .take(MAX_DIFFERENCES)
+        .map(|difference| {
+            reflection::Product::new(
+                format.render_path(difference.pointer()),
+                difference.to_string(),
+            )
+        })
+        .collect();
+    if MAX_DIFFERENCES < differences.len() {
+        products.push(reflection::Product::new(
+            "more differences",
+            differences.len() - MAX_DIFFERENCES,
+        ));
+    }
+    products
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn diff_lists_pointers() {
+        let expected = json!({"a": {"b": [1, 2, 3]}, "c/d": true, "e": null});
+        let actual = json!({"a": {"b": [1, 5]}, "c/d": false, "f": 1.0});
+        let differences: Vec<_> = diff(&expected, &actual, false)
+            .iter()
+            .map(|d| format!("{}: {d}", d.pointer()))
+            .collect();
+        assert_eq!(
+            differences,
+            [
+                "/a/b/1: expected 2, found 5",
+                "/a/b/2: missing, expected 3",
+                "/c~1d: expected true, found false",
+                "/e: missing, expected null",
+                "/f: unexpected 1.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn subset_ignores_extras() {
+        let expected = json!({"a": [1], "b": 2});
+        let actual = json!({"a": [1.0, 2], "b": 2, "c": 3});
+        assert_eq!(diff(&expected, &actual, true), []);
+        assert_eq!(diff(&expected, &actual, false).len(), 2);
+    }
+
+    #[test]
+    fn large_integers_compare_exactly() {
+        let expected = json!(9007199254740993u64);
+        assert_eq!(diff(&expected, &json!(9007199254740992u64), false).len(), 1);
+        assert_eq!(diff(&expected, &json!(9007199254740993u64), false), []);
+        assert_eq!(diff(&json!(i64::MIN), &json!(i64::MIN + 1), false).len(), 1);
+        assert_eq!(diff(&json!(2), &json!(2.0), false), []);
+    }
+}
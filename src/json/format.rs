@@ -0,0 +1,166 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Document formats that are compared as JSON value trees.
+
+use serde_json::Value;
+
+/// The format of documents passed as `str` or `[u8]`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    Json,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl Format {
+    /// Parse a document, describing any error with its line and column.
+    pub(crate) fn parse_str(self, variable: &str) -> Result<Value, String> {
+        match self {
+            Format::Json => serde_json::from_str(variable).map_err(|err| err.to_string()),
+            #[cfg(feature = "toml")]
+            Format::Toml => toml::from_str::<toml::Table>(variable)
+                .map(|table| from_toml(toml::Value::Table(table)))
+                .map_err(|err| err.to_string().trim_end().to_owned()),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_norway::from_str(variable)
+                .map(from_yaml)
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Parse a document, describing any error with its line and column.
+    pub(crate) fn parse_bytes(self, variable: &[u8]) -> Result<Value, String> {
+        match self {
+            Format::Json => serde_json::from_slice(variable).map_err(|err| err.to_string()),
+            #[cfg(feature = "toml")]
+            Format::Toml => std::str::from_utf8(variable)
+                .map_err(|err| err.to_string())
+                .and_then(|variable| self.parse_str(variable)),
+            #[cfg(feature = "yaml")]
+            Format::Yaml => serde_norway::from_slice(variable)
+                .map(from_yaml)
+                .map_err(|err| err.to_string()),
+        }
+    }
+
+    /// Render a JSON pointer the way paths are written for this format.
+    ///
+    /// JSON keeps the pointer; other formats use dotted keys, quoting keys that contain a dot.
+    pub(crate) fn render_path(self, pointer: &str) -> String {
+        if pointer.is_empty() {
+            return "(root)".to_owned();
+        }
+        match self {
+            Format::Json => pointer.to_owned(),
+            #[cfg(any(feature = "toml", feature = "yaml"))]
+            _ => pointer[1..]
+                .split('/')
+                .map(|token| {
+                    let key = token.replace("~1", "/").replace("~0", "~");
+                    if key.is_empty() || key.contains(['.', '"']) {
+                        format!("{key:?}")
+                    } else {
+                        key
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("."),
+        }
+    }
+
+    /// The name of the path lookup for this format.
+    pub(crate) fn path_name(self) -> &'static str {
+        match self {
+            Format::Json => "pointer",
+            #[cfg(any(feature = "toml", feature = "yaml"))]
+            _ => "path",
+        }
+    }
+}
+
+/// Convert a dotted key path, like `server.ports.0`, to a JSON pointer.
+///
+/// Paths starting with `/` are taken to already be JSON pointers.
+#[cfg(any(feature = "toml", feature = "yaml"))]
+pub(crate) fn pointer_from_path(path: &str) -> String {
+    if path.is_empty() || path.starts_with('/') {
+        return path.to_owned();
+    }
+    path.split('.')
+        .map(|key| format!("/{}", key.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Convert a TOML value, rendering datetimes as strings.
+#[cfg(feature = "toml")]
+pub(crate) fn from_toml(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(a) => Value::Array(a.into_iter().map(from_toml).collect()),
+        toml::Value::Table(t) => {
+            Value::Object(t.into_iter().map(|(k, v)| (k, from_toml(v))).collect())
+        }
+    }
+}
+
+/// Convert a YAML value, rendering non-string keys as their YAML text and dropping tags.
+#[cfg(feature = "yaml")]
+pub(crate) fn from_yaml(value: serde_norway::Value) -> Value {
+    match value {
+        serde_norway::Value::Null => Value::Null,
+        serde_norway::Value::Bool(b) => Value::Bool(b),
+        serde_norway::Value::Number(n) => match (n.as_i64(), n.as_u64(), n.as_f64()) {
+            (Some(i), _, _) => Value::from(i),
+            (_, Some(u), _) => Value::from(u),
+            (_, _, Some(f)) => Value::from(f),
+            _ => Value::Null,
+        },
+        serde_norway::Value::String(s) => Value::String(s),
+        serde_norway::Value::Sequence(s) => Value::Array(s.into_iter().map(from_yaml).collect()),
+        serde_norway::Value::Mapping(m) => Value::Object(
+            m.into_iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        serde_norway::Value::String(s) => s,
+                        k => serde_norway::to_string(&k)
+                            .map(|s| s.trim_end().to_owned())
+                            .unwrap_or_default(),
+                    };
+                    (key, from_yaml(v))
+                })
+                .collect(),
+        ),
+        serde_norway::Value::Tagged(t) => from_yaml(t.value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "toml", feature = "yaml"))]
+    fn dotted_paths() {
+        assert_eq!(pointer_from_path("server.ports.0"), "/server/ports/0");
+        assert_eq!(pointer_from_path("/a.b/c"), "/a.b/c");
+        assert_eq!(pointer_from_path("a/b"), "/a~1b");
+    }
+
+    #[test]
+    fn render_json_pointer() {
+        assert_eq!(Format::Json.render_path(""), "(root)");
+        assert_eq!(Format::Json.render_path("/a~1b/0"), "/a~1b/0");
+    }
+}
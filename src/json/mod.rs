@@ -0,0 +1,22 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! JSON Predicates
+//!
+//! This module contains predicates that compare JSON documents by structure, rather than by
+//! text.  They accept JSON text as `str` or `[u8]`, or an already parsed
+//! [`serde_json::Value`].
+
+mod basics;
+mod diff;
+pub(crate) mod format;
+pub use self::basics::*;
+#[cfg(feature = "serde")]
+mod adapters;
+#[cfg(feature = "serde")]
+pub use self::adapters::*;
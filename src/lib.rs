@@ -139,16 +139,75 @@
 //!   of the given file.
 //! - [`predicate::str::diff`]: Same as `eq` except report a diff.  See [`DifferencePredicate`]
 //!   for more features.
+//! - [`predicate::str::matches_snapshot`]: Same as `diff` except `[..]` and `...` act as
+//!   wildcards.
+//! - [`predicate::str::similar_to`]: Specified string must be within an edit distance or
+//!   similarity ratio of the given string.
 //! - [`predicate::str::starts_with`]: Specified string must start with the given needle.
 //! - [`predicate::str::ends_with`]: Specified string must end with the given needle.
 //! - [`predicate::str::contains`]: Specified string must contain the given needle.
 //!   - [`predicate::str::contains(...).count`]: Required number of times the needle must show up.
 //! - [`predicate::str::is_match`]: Specified string must match the given regex.
 //!   - [`predicate::str::is_match(...).count`]: Required number of times the match must show up.
+//!   - [`predicate::str::is_match(...).capture`]: Capture groups of the first match must satisfy
+//!     the given predicates.
+//! - [`predicate::str::matches_any`]: Specified string must match at least one of the given
+//!   regexes.
+//! - [`predicate::str::matches_all`]: Specified string must match every one of the given regexes.
+//! - [`predicate::str::glob`]: Specified string must match the given glob pattern.
+//! - [`predicate::str::matches_file`]: Specified string must equal the contents of a golden file,
+//!   which can be rewritten by setting `PREDICATES_UPDATE=1`.
 //! - [`str_pred.trim`]: Trim whitespace before passing it to `str_pred`.
 //! - [`str_pred.normalize`]: Normalize the line endings before passing it to `str_pred`.
+//! - [`str_pred.redact`]: Rewrite volatile data, like timestamps, before passing it to `str_pred`.
 //! - [`bytes_pred = str_pred.from_utf8()`]: Reuse string predicates in other contexts, like the
 //!   file system.
+//! - [`bytes_pred = str_pred.from_utf8_lossy()`]: Same as `from_utf8` except invalid UTF-8 is
+//!   replaced with U+FFFD.
+//! - [`bytes_pred = str_pred.from_text()`]: Same as `from_utf8` except UTF-16 is decoded when
+//!   there is a byte order mark.  See also `from_utf16le`, `from_utf16be`, and `from_latin1`.
+//!
+//! `[u8]` predicates
+//! - [`predicate::bytes::is_empty`]: Specified bytes must be empty.
+//! - [`predicate::bytes::eq`]: Specified bytes must equal the given bytes, reporting a hexdump
+//!   diff.
+//! - [`predicate::bytes::starts_with`]: Specified bytes must start with the given needle.
+//! - [`predicate::bytes::ends_with`]: Specified bytes must end with the given needle.
+//! - [`predicate::bytes::contains`]: Specified bytes must contain the given needle.
+//!   - [`predicate::bytes::contains(...).count`]: Required number of times the needle must show
+//!     up.
+//! - [`str_pred = bytes_pred.from_base64()`]: Decode base64 before passing it to `bytes_pred`.
+//! - [`str_pred = bytes_pred.from_hex()`]: Decode hex before passing it to `bytes_pred`.
+//! - [`bytes_pred.normalize_newlines`]: Normalize the line endings before passing it to
+//!   `bytes_pred`.
+//! - [`predicate::bytes::is_match`]: Specified bytes must match the given regex.
+//!   - [`predicate::bytes::is_match(...).count`]: Required number of times the match must show up.
+//!
+//! `OsStr` predicates
+//! - [`predicate::os_str::eq`]: Specified `OsStr` must equal the given `OsStr`.
+//! - [`predicate::os_str::starts_with`]: Specified `OsStr` must start with the given needle.
+//! - [`predicate::os_str::contains`]: Specified `OsStr` must contain the given needle.
+//!
+//! JSON predicates (`json` feature)
+//! - [`predicate::json::eq`]: Specified JSON document must structurally equal the given value.
+//! - [`predicate::json::is_subset_of`]: Given value must be a subset of the specified JSON
+//!   document.
+//! - [`predicate::json::pointer`]: The node at the given JSON pointer must match the given
+//!   predicate.
+//! - [`pred = value_pred.from_serialize()`]: Serialize any `serde::Serialize` item before
+//!   passing it to `value_pred` (`serde` feature).
+//!
+//! TOML predicates (`toml` feature)
+//! - [`predicate::toml::eq`]: Specified TOML document must structurally equal the given value.
+//! - [`predicate::toml::is_subset_of`]: Given value must be a subset of the specified TOML
+//!   document.
+//! - [`predicate::toml::path`]: The value at the given key path must match the given predicate.
+//!
+//! YAML predicates (`yaml` feature)
+//! - [`predicate::yaml::eq`]: Specified YAML document must structurally equal the given value.
+//! - [`predicate::yaml::is_subset_of`]: Given value must be a subset of the specified YAML
+//!   document.
+//! - [`predicate::yaml::path`]: The value at the given key path must match the given predicate.
 //!
 //! File system predicates
 //! - [`predicate::path::exists`]: Specified path must exist on disk.
@@ -156,18 +215,43 @@
 //! - [`predicate::path::is_dir`]: Specified path is a directory.
 //! - [`predicate::path::is_file`]: Specified path is a file.
 //! - [`predicate::path::is_symlink`]: Specified path is a symlink.
+//! - [`predicate::path::size`]: Specified path's size must match the given predicate.
+//! - [`predicate::path::is_readonly`]: Specified path is read-only.
+//! - [`predicate::path::mode`]: Specified path's permission bits must match the given predicate
+//!   (unix).
+//! - [`predicate::path::is_executable`]: Specified path has an execute bit set (unix).
+//! - [`predicate::path::owner_uid`]: Specified path's owner must match the given predicate (unix).
+//! - [`predicate::path::nlink`]: Specified path's hard link count must match the given predicate
+//!   (unix).
 //! - [`path_pred = predicate::path::eq_file`]: Specified path's contents must equal the contents of the given
 //!   file.
+//!   - [`predicate::path::try_eq_file`]: Same as `eq_file`, returning an error if the file can't
+//!     be read.
+//!   - [`predicate::path::eq_file_lazy`]: Same as `eq_file`, reading the file on first use.
+//! - [`predicate::path::snapshot`]: Same as `predicate::str::matches_file`, for strings, bytes,
+//!   or the contents of the specified path.
 //! - [`path_pred = bytes_pred.from_file_path`]: Specified path's contents must equal the `bytes_pred`.
 //!
 //! [`DifferencePredicate`]: crate::str::DifferencePredicate
+//! [`bytes_pred.normalize_newlines`]: prelude::PredicateBytesExt::normalize_newlines()
+//! [`bytes_pred = str_pred.from_text()`]: prelude::PredicateStrExt::from_text()
 //! [`bytes_pred = str_pred.from_utf8()`]: prelude::PredicateStrExt::from_utf8()
+//! [`bytes_pred = str_pred.from_utf8_lossy()`]: prelude::PredicateStrExt::from_utf8_lossy()
 //! [`path_pred = bytes_pred.from_file_path`]: prelude::PredicateFileContentExt::from_file_path()
 //! [`path_pred = predicate::path::eq_file`]: prelude::predicate::path::eq_file()
+//! [`pred = value_pred.from_serialize()`]: prelude::PredicateValueExt::from_serialize()
 //! [`pred_a.and(pred_b)`]: boolean::PredicateBooleanExt::and()
 //! [`pred_a.not()`]: boolean::PredicateBooleanExt::not()
 //! [`pred_a.or(pred_b)`]: boolean::PredicateBooleanExt::or()
 //! [`predicate::always`]: constant::always()
+//! [`predicate::bytes::contains(...).count`]: bytes::ContainsPredicate::count()
+//! [`predicate::bytes::contains`]: prelude::predicate::bytes::contains()
+//! [`predicate::bytes::ends_with`]: prelude::predicate::bytes::ends_with()
+//! [`predicate::bytes::eq`]: prelude::predicate::bytes::eq()
+//! [`predicate::bytes::is_empty`]: prelude::predicate::bytes::is_empty()
+//! [`predicate::bytes::is_match(...).count`]: bytes::RegexPredicate::count()
+//! [`predicate::bytes::is_match`]: prelude::predicate::bytes::is_match()
+//! [`predicate::bytes::starts_with`]: prelude::predicate::bytes::starts_with()
 //! [`predicate::eq`]: ord::eq()
 //! [`predicate::float::is_close`]: prelude::predicate::float::is_close()
 //! [`predicate::function`]: function::function()
@@ -176,26 +260,57 @@
 //! [`predicate::in_hash`]: iter::in_hash()
 //! [`predicate::in_iter(...).sort`]: iter::InPredicate::sort()
 //! [`predicate::in_iter`]: iter::in_iter()
+//! [`predicate::json::eq`]: prelude::predicate::json::eq()
+//! [`predicate::json::is_subset_of`]: prelude::predicate::json::is_subset_of()
+//! [`predicate::json::pointer`]: prelude::predicate::json::pointer()
 //! [`predicate::le`]: ord::le()
 //! [`predicate::lt`]: ord::lt()
 //! [`predicate::name`]: name::PredicateNameExt::name()
 //! [`predicate::ne`]: ord::ne()
 //! [`predicate::never`]: constant::never()
+//! [`predicate::os_str::contains`]: prelude::predicate::os_str::contains()
+//! [`predicate::os_str::eq`]: prelude::predicate::os_str::eq()
+//! [`predicate::os_str::starts_with`]: prelude::predicate::os_str::starts_with()
+//! [`predicate::path::eq_file_lazy`]: prelude::predicate::path::eq_file_lazy()
 //! [`predicate::path::exists`]: prelude::predicate::path::exists()
 //! [`predicate::path::is_dir`]: prelude::predicate::path::is_dir()
 //! [`predicate::path::is_file`]: prelude::predicate::path::is_file()
+//! [`predicate::path::is_executable`]: prelude::predicate::path::is_executable()
+//! [`predicate::path::is_readonly`]: prelude::predicate::path::is_readonly()
 //! [`predicate::path::is_symlink`]: prelude::predicate::path::is_symlink()
 //! [`predicate::path::missing`]: prelude::predicate::path::missing()
+//! [`predicate::path::mode`]: prelude::predicate::path::mode()
+//! [`predicate::path::nlink`]: prelude::predicate::path::nlink()
+//! [`predicate::path::owner_uid`]: prelude::predicate::path::owner_uid()
+//! [`predicate::path::size`]: prelude::predicate::path::size()
+//! [`predicate::path::snapshot`]: prelude::predicate::path::snapshot()
+//! [`predicate::path::try_eq_file`]: prelude::predicate::path::try_eq_file()
 //! [`predicate::str::contains(...).count`]: str::ContainsPredicate::count()
 //! [`predicate::str::contains`]: prelude::predicate::str::contains()
 //! [`predicate::str::diff`]: prelude::predicate::str::diff()
 //! [`predicate::str::ends_with`]: prelude::predicate::str::ends_with()
+//! [`predicate::str::glob`]: prelude::predicate::str::glob()
 //! [`predicate::str::is_empty`]: prelude::predicate::str::is_empty()
+//! [`predicate::str::is_match(...).capture`]: str::RegexPredicate::capture()
 //! [`predicate::str::is_match(...).count`]: str::RegexPredicate::count()
 //! [`predicate::str::is_match`]: prelude::predicate::str::is_match()
+//! [`predicate::str::matches_all`]: prelude::predicate::str::matches_all()
+//! [`predicate::str::matches_any`]: prelude::predicate::str::matches_any()
+//! [`predicate::str::matches_file`]: prelude::predicate::str::matches_file()
+//! [`predicate::str::matches_snapshot`]: prelude::predicate::str::matches_snapshot()
+//! [`predicate::str::similar_to`]: prelude::predicate::str::similar_to()
 //! [`predicate::str::starts_with`]: prelude::predicate::str::starts_with()
+//! [`predicate::toml::eq`]: prelude::predicate::toml::eq()
+//! [`predicate::toml::is_subset_of`]: prelude::predicate::toml::is_subset_of()
+//! [`predicate::toml::path`]: prelude::predicate::toml::path()
+//! [`predicate::yaml::eq`]: prelude::predicate::yaml::eq()
+//! [`predicate::yaml::is_subset_of`]: prelude::predicate::yaml::is_subset_of()
+//! [`predicate::yaml::path`]: prelude::predicate::yaml::path()
 //! [`str_pred = predicate::path::eq_file(...).utf8`]: path::BinaryFilePredicate::utf8()
+//! [`str_pred = bytes_pred.from_base64()`]: prelude::PredicateBytesExt::from_base64()
+//! [`str_pred = bytes_pred.from_hex()`]: prelude::PredicateBytesExt::from_hex()
 //! [`str_pred.normalize`]: prelude::PredicateStrExt::normalize()
+//! [`str_pred.redact`]: prelude::PredicateStrExt::redact()
 //! [`str_pred.trim`]: prelude::PredicateStrExt::trim()
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -220,12 +335,22 @@ pub mod ord;
 pub mod boolean;
 
 // specialized primitive `Predicate` types
+pub mod bytes;
 pub mod float;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod os_str;
 pub mod path;
 pub mod str;
+#[cfg(feature = "toml")]
+pub mod toml;
+#[cfg(feature = "yaml")]
+pub mod yaml;
 
 mod color;
 use color::Palette;
+mod distance;
+mod hexdump;
 mod utils;
 
 #[doc = include_str!("../README.md")]
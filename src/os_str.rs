@@ -0,0 +1,260 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `OsStr` Predicates
+//!
+//! This module contains predicates that compare `OsStr`s directly, without requiring them to be
+//! valid UTF-8.
+
+use std::ffi;
+use std::fmt;
+
+use crate::Predicate;
+use crate::reflection;
+use crate::utils;
+
+fn find_case<'a, P>(
+    pred: &'a P,
+    expected: bool,
+    variable: &ffi::OsStr,
+) -> Option<reflection::Case<'a>>
+where
+    P: Predicate<ffi::OsStr>,
+{
+    utils::default_find_case(pred, expected, variable).map(|case| {
+        case.add_product(reflection::Product::new(
+            "var",
+            utils::DebugAdapter::new(variable.to_owned()),
+        ))
+    })
+}
+
+/// Predicate checks start of an `OsStr`
+///
+/// This is created by `predicates::os_str::starts_with`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartsWithPredicate {
+    pattern: ffi::OsString,
+}
+
+impl Predicate<ffi::OsStr> for StartsWithPredicate {
+    fn eval(&self, variable: &ffi::OsStr) -> bool {
+        variable
+            .as_encoded_bytes()
+            .starts_with(self.pattern.as_encoded_bytes())
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &ffi::OsStr,
+    ) -> Option<reflection::Case<'a>> {
+        find_case(self, expected, variable)
+    }
+}
+
+impl reflection::PredicateReflection for StartsWithPredicate {}
+
+impl fmt::Display for StartsWithPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("starts_with"),
+            palette.expected(format_args!("{:?}", self.pattern))
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures an `OsStr` starts with `pattern`
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::os_str::starts_with("--");
+/// assert_eq!(true, predicate_fn.eval(OsStr::new("--verbose")));
+/// assert_eq!(false, predicate_fn.eval(OsStr::new("-v")));
+/// ```
+pub fn starts_with<P>(pattern: P) -> StartsWithPredicate
+where
+    P: Into<ffi::OsString>,
+{
+    StartsWithPredicate {
+        pattern: pattern.into(),
+    }
+}
+
+/// Predicate that checks for patterns in an `OsStr`
+///
+/// This is created by `predicates::os_str::contains`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainsPredicate {
+    pattern: ffi::OsString,
+}
+
+impl Predicate<ffi::OsStr> for ContainsPredicate {
+    fn eval(&self, variable: &ffi::OsStr) -> bool {
+        let pattern = self.pattern.as_encoded_bytes();
+        pattern.is_empty()
+            || variable
+                .as_encoded_bytes()
+                .windows(pattern.len())
+                .any(|window| window == pattern)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &ffi::OsStr,
+    ) -> Option<reflection::Case<'a>> {
+        find_case(self, expected, variable)
+    }
+}
+
+impl reflection::PredicateReflection for ContainsPredicate {}
+
+impl fmt::Display for ContainsPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("contains"),
+            palette.expected(format_args!("{:?}", self.pattern))
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures an `OsStr` contains `pattern`
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::os_str::contains("=");
+/// assert_eq!(true, predicate_fn.eval(OsStr::new("--color=always")));
+/// assert_eq!(false, predicate_fn.eval(OsStr::new("--color")));
+/// ```
+pub fn contains<P>(pattern: P) -> ContainsPredicate
+where
+    P: Into<ffi::OsString>,
+{
+    ContainsPredicate {
+        pattern: pattern.into(),
+    }
+}
+
+/// Predicate that checks an `OsStr` for equality
+///
+/// This is created by `predicates::os_str::eq`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EqPredicate {
+    orig: ffi::OsString,
+}
+
+impl Predicate<ffi::OsStr> for EqPredicate {
+    fn eval(&self, variable: &ffi::OsStr) -> bool {
+        self.orig == variable
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &ffi::OsStr,
+    ) -> Option<reflection::Case<'a>> {
+        find_case(self, expected, variable)
+    }
+}
+
+impl reflection::PredicateReflection for EqPredicate {}
+
+impl fmt::Display for EqPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.var("var"),
+            palette.description("=="),
+            palette.expected(format_args!("{:?}", self.orig))
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures an `OsStr` equals `orig`
+///
+/// # Examples
+///
+/// ```
+/// use std::ffi::OsStr;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::os_str::eq("target");
+/// assert_eq!(true, predicate_fn.eval(OsStr::new("target")));
+/// assert_eq!(false, predicate_fn.eval(OsStr::new("Target")));
+/// ```
+pub fn eq<P>(orig: P) -> EqPredicate
+where
+    P: Into<ffi::OsString>,
+{
+    EqPredicate { orig: orig.into() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::str::PredicateStrExt as _;
+
+    fn products(case: &reflection::Case<'_>) -> Vec<(String, String)> {
+        case.products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_var() {
+        let p = starts_with("--");
+        let case = p.find_case(false, ffi::OsStr::new("-v")).unwrap();
+        assert_eq!(products(&case), [("var".to_owned(), "\"-v\"".to_owned())]);
+        assert!(p.find_case(false, ffi::OsStr::new("--verbose")).is_none());
+
+        let p = contains("=");
+        let case = p.find_case(false, ffi::OsStr::new("--color")).unwrap();
+        assert_eq!(products(&case)[0].1, "\"--color\"");
+
+        let p = eq("target");
+        let case = p.find_case(true, ffi::OsStr::new("target")).unwrap();
+        assert_eq!(products(&case)[0].1, "\"target\"");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn lossy_reports_replaced_bytes() {
+        use std::os::unix::ffi::OsStrExt as _;
+
+        let p = crate::str::starts_with("Hello").from_utf8_lossy();
+        let variable = ffi::OsStr::from_bytes(b"\xFFHello\xE2\x82");
+        let case = p.find_case(false, variable).unwrap();
+        let products = products(&case);
+        assert_eq!(
+            products[products.len() - 2..],
+            [
+                ("var as str".to_owned(), "\u{FFFD}Hello\u{FFFD}".to_owned()),
+                ("replaced bytes".to_owned(), "0x0..0x1, 0x6..0x8".to_owned()),
+            ]
+        );
+    }
+}
@@ -10,8 +10,10 @@ use std::fmt;
 use std::fs;
 use std::io::{self, Read};
 use std::path;
+use std::sync;
 
 use crate::Predicate;
+use crate::hexdump;
 use crate::reflection;
 use crate::utils;
 
@@ -21,17 +23,106 @@ fn read_file(path: &path::Path) -> io::Result<Vec<u8>> {
     Ok(buffer)
 }
 
+/// Compare `variable` to a file's `content`, explaining the first difference.
+fn find_bytes_case<'a>(
+    predicate: &'a dyn reflection::PredicateReflection,
+    content: &[u8],
+    expected: bool,
+    variable: &[u8],
+) -> Option<reflection::Case<'a>> {
+    let actual = content == variable;
+    if actual != expected {
+        return None;
+    }
+
+    let case = reflection::Case::new(Some(predicate), actual);
+    Some(
+        hexdump::products(content, variable)
+            .into_iter()
+            .fold(case, |case, product| case.add_product(product)),
+    )
+}
+
+fn read_str_file(path: &path::Path) -> io::Result<String> {
+    String::from_utf8(read_file(path)?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Find the case for a file's `content`, naming the file as `var` and reporting read errors.
+pub(crate) fn find_path_case<'a, C>(
+    predicate: &'a dyn reflection::PredicateReflection,
+    expected: bool,
+    variable: &path::Path,
+    content: io::Result<C>,
+    find_content_case: impl FnOnce(C) -> Option<reflection::Case<'a>>,
+) -> Option<reflection::Case<'a>> {
+    let var = reflection::Product::new("var", variable.display().to_string());
+    match (expected, content) {
+        (_, Ok(content)) => find_content_case(content).map(|case| case.add_product(var)),
+        (true, Err(_)) => None,
+        (false, Err(err)) => Some(
+            reflection::Case::new(Some(predicate), false)
+                .add_product(var)
+                .add_product(reflection::Product::new("error", err)),
+        ),
+    }
+}
+
 /// Predicate that compares file matches
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BinaryFilePredicate {
     path: path::PathBuf,
-    content: utils::DebugAdapter<Vec<u8>>,
+    content: utils::BytesAdapter,
+    normalize_newlines: bool,
 }
 
 impl BinaryFilePredicate {
+    /// Ignore the difference between `\n`, `\r\n`, and `\r` line endings.
+    ///
+    /// Both the file content and the variable are normalized to `\n` before comparing.  This
+    /// carries over to [`utf8`][BinaryFilePredicate::utf8].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_file = predicate::path::eq_file(Path::new("Cargo.toml")).normalize_newlines();
+    /// let content = std::fs::read_to_string("Cargo.toml").unwrap();
+    /// assert_eq!(true, predicate_file.eval(content.replace('\n', "\r\n").as_bytes()));
+    /// ```
+    #[cfg(feature = "normalize-line-endings")]
+    pub fn normalize_newlines(mut self) -> Self {
+        self.content = utils::BytesAdapter(crate::bytes::normalize::normalized(&self.content.0));
+        self.normalize_newlines = true;
+        self
+    }
+
+    fn normalize_variable<'s>(&self, variable: &'s [u8]) -> std::borrow::Cow<'s, [u8]> {
+        #[cfg(feature = "normalize-line-endings")]
+        if self.normalize_newlines {
+            return std::borrow::Cow::Owned(crate::bytes::normalize::normalized(variable));
+        }
+        std::borrow::Cow::Borrowed(variable)
+    }
+
     fn eval(&self, path: &path::Path) -> io::Result<bool> {
         let content = read_file(path)?;
-        Ok(self.content.debug == content)
+        Ok(self.content.0 == *self.normalize_variable(&content))
+    }
+
+    fn find_content_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &[u8],
+    ) -> Option<reflection::Case<'a>> {
+        find_bytes_case(
+            self,
+            &self.content.0,
+            expected,
+            &self.normalize_variable(variable),
+        )
     }
 
     /// Creates a new `Predicate` that ensures complete equality
@@ -51,8 +142,16 @@ impl BinaryFilePredicate {
     /// ```
     pub fn utf8(self) -> Option<StrFilePredicate> {
         let path = self.path;
-        let content = String::from_utf8(self.content.debug).ok()?;
-        Some(StrFilePredicate { path, content })
+        let content = String::from_utf8(self.content.0).ok()?;
+        #[cfg(feature = "diff")]
+        let diff = crate::str::DiffOptions::default().labels(path.display().to_string(), "var");
+        Some(StrFilePredicate {
+            path,
+            content,
+            normalize_newlines: self.normalize_newlines,
+            #[cfg(feature = "diff")]
+            diff,
+        })
     }
 }
 
@@ -66,17 +165,19 @@ impl Predicate<path::Path> for BinaryFilePredicate {
         expected: bool,
         variable: &path::Path,
     ) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
+        find_path_case(self, expected, variable, read_file(variable), |buffer| {
+            self.find_content_case(expected, &buffer)
+        })
     }
 }
 
 impl Predicate<[u8]> for BinaryFilePredicate {
     fn eval(&self, actual: &[u8]) -> bool {
-        self.content.debug == actual
+        self.content.0 == *self.normalize_variable(actual)
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
+        self.find_content_case(expected, variable)
     }
 }
 
@@ -102,6 +203,10 @@ impl fmt::Display for BinaryFilePredicate {
 
 /// Creates a new `Predicate` that ensures complete equality
 ///
+/// # Panics
+///
+/// If the file can't be read.  See [`try_eq_file`] and [`eq_file_lazy`] for alternatives.
+///
 /// # Examples
 ///
 /// ```
@@ -114,8 +219,154 @@ impl fmt::Display for BinaryFilePredicate {
 /// ```
 pub fn eq_file<P: Into<path::PathBuf>>(path: P) -> BinaryFilePredicate {
     let path = path.into();
-    let content = utils::DebugAdapter::new(read_file(&path).unwrap());
-    BinaryFilePredicate { path, content }
+    try_eq_file(&path).unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()))
+}
+
+/// Creates a new `Predicate` that ensures complete equality, failing if the file can't be read
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_file = predicate::path::try_eq_file(Path::new("Cargo.toml")).unwrap();
+/// assert_eq!(true, predicate_file.eval(Path::new("Cargo.toml")));
+///
+/// assert!(predicate::path::try_eq_file(Path::new("non-existent-file.foo")).is_err());
+/// ```
+pub fn try_eq_file<P: Into<path::PathBuf>>(path: P) -> io::Result<BinaryFilePredicate> {
+    let path = path.into();
+    let content = utils::BytesAdapter(read_file(&path)?);
+    Ok(BinaryFilePredicate {
+        path,
+        content,
+        normalize_newlines: false,
+    })
+}
+
+/// Predicate that compares file matches, reading the expected file on first use
+///
+/// This is created by the `predicate::path::eq_file_lazy`.
+#[derive(Debug, Clone)]
+pub struct LazyFilePredicate {
+    path: path::PathBuf,
+    content: sync::OnceLock<Result<Vec<u8>, sync::Arc<io::Error>>>,
+}
+
+impl LazyFilePredicate {
+    fn content(&self) -> Result<&[u8], &io::Error> {
+        self.content
+            .get_or_init(|| read_file(&self.path).map_err(sync::Arc::new))
+            .as_deref()
+            .map_err(|err| &**err)
+    }
+
+    fn find_content_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &[u8],
+    ) -> Option<reflection::Case<'a>> {
+        match self.content() {
+            Ok(content) => find_bytes_case(self, content, expected, variable),
+            Err(_) if expected => None,
+            Err(err) => Some(
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new(
+                        "expected file",
+                        self.path.display().to_string(),
+                    ))
+                    .add_product(reflection::Product::new("error", err.to_string())),
+            ),
+        }
+    }
+}
+
+impl Predicate<path::Path> for LazyFilePredicate {
+    fn eval(&self, path: &path::Path) -> bool {
+        match (self.content(), read_file(path)) {
+            (Ok(content), Ok(actual)) => content == actual,
+            _ => false,
+        }
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        let buffer = read_file(variable);
+        let unreadable = buffer.is_err();
+        let case = find_path_case(self, expected, variable, buffer, |buffer| {
+            self.find_content_case(expected, &buffer)
+        })?;
+        match self.content() {
+            // Both sides failed; the missing fixture is usually the one to fix.
+            Err(err) if unreadable => Some(
+                case.add_product(reflection::Product::new(
+                    "expected file",
+                    self.path.display().to_string(),
+                ))
+                .add_product(reflection::Product::new(
+                    "expected file error",
+                    err.to_string(),
+                )),
+            ),
+            _ => Some(case),
+        }
+    }
+}
+
+impl Predicate<[u8]> for LazyFilePredicate {
+    fn eval(&self, actual: &[u8]) -> bool {
+        self.content()
+            .map(|content| content == actual)
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        self.find_content_case(expected, variable)
+    }
+}
+
+impl reflection::PredicateReflection for LazyFilePredicate {}
+
+impl fmt::Display for LazyFilePredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.var("var"),
+            palette.description("is"),
+            palette.expected(self.path.display())
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures complete equality, reading the file on first use
+///
+/// Unlike [`eq_file`], a missing or unreadable file doesn't panic; it fails the predicate, with
+/// the path and error reported by `find_case`.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_file = predicate::path::eq_file_lazy(Path::new("Cargo.toml"));
+/// assert_eq!(true, predicate_file.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_file.eval(Path::new("src")));
+///
+/// let predicate_file = predicate::path::eq_file_lazy(Path::new("non-existent-file.foo"));
+/// assert_eq!(false, predicate_file.eval(Path::new("Cargo.toml")));
+/// ```
+pub fn eq_file_lazy<P: Into<path::PathBuf>>(path: P) -> LazyFilePredicate {
+    LazyFilePredicate {
+        path: path.into(),
+        content: sync::OnceLock::new(),
+    }
 }
 
 /// Predicate that compares string content of files
@@ -123,13 +374,96 @@ pub fn eq_file<P: Into<path::PathBuf>>(path: P) -> BinaryFilePredicate {
 pub struct StrFilePredicate {
     path: path::PathBuf,
     content: String,
+    normalize_newlines: bool,
+    #[cfg(feature = "diff")]
+    diff: crate::str::DiffOptions,
 }
 
 impl StrFilePredicate {
-    fn eval(&self, path: &path::Path) -> Option<bool> {
-        let content = read_file(path).ok()?;
-        let content = String::from_utf8(content).ok()?;
-        Some(self.content == content)
+    /// Ignore the difference between `\n`, `\r\n`, and `\r` line endings.
+    ///
+    /// Both the file content and the variable are normalized to `\n` before comparing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_file = predicate::path::eq_file(Path::new("Cargo.toml"))
+    ///     .utf8()
+    ///     .unwrap()
+    ///     .normalize_newlines();
+    /// let content = std::fs::read_to_string("Cargo.toml").unwrap();
+    /// assert_eq!(true, predicate_file.eval(content.replace('\n', "\r\n").as_str()));
+    /// ```
+    #[cfg(feature = "normalize-line-endings")]
+    pub fn normalize_newlines(mut self) -> Self {
+        self.content = normalize_line_endings::normalized(self.content.chars()).collect();
+        self.normalize_newlines = true;
+        self
+    }
+
+    /// Render a failure's diff with the file content and the variable side by side.
+    ///
+    /// `width` is the total width of the output, split between the two columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_file = predicate::path::eq_file(Path::new("Cargo.toml"))
+    ///     .utf8()
+    ///     .unwrap()
+    ///     .side_by_side(80);
+    /// assert_eq!(false, predicate_file.eval("Not a real Cargo.toml file content"));
+    /// ```
+    #[cfg(feature = "diff")]
+    pub fn side_by_side(mut self, width: usize) -> Self {
+        self.diff = self.diff.side_by_side(width);
+        self
+    }
+
+    fn normalize_variable<'s>(&self, variable: &'s str) -> std::borrow::Cow<'s, str> {
+        #[cfg(feature = "normalize-line-endings")]
+        if self.normalize_newlines {
+            return std::borrow::Cow::Owned(
+                normalize_line_endings::normalized(variable.chars()).collect(),
+            );
+        }
+        std::borrow::Cow::Borrowed(variable)
+    }
+
+    fn eval(&self, path: &path::Path) -> io::Result<bool> {
+        let content = read_str_file(path)?;
+        Ok(self.content == self.normalize_variable(&content))
+    }
+
+    fn find_content_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &str,
+    ) -> Option<reflection::Case<'a>> {
+        let variable = self.normalize_variable(variable);
+        let actual = self.content == variable;
+        if actual != expected {
+            return None;
+        }
+
+        let case = reflection::Case::new(Some(self), actual);
+        if actual {
+            return Some(case);
+        }
+        #[cfg(feature = "diff")]
+        let case = case.add_product(reflection::Product::new(
+            "diff",
+            self.diff.render(&self.content, &variable),
+        ));
+        #[cfg(not(feature = "diff"))]
+        let case = case.add_product(reflection::Product::new("var", variable.into_owned()));
+        Some(case)
     }
 }
 
@@ -143,17 +477,23 @@ impl Predicate<path::Path> for StrFilePredicate {
         expected: bool,
         variable: &path::Path,
     ) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
+        find_path_case(
+            self,
+            expected,
+            variable,
+            read_str_file(variable),
+            |content| self.find_content_case(expected, &content),
+        )
     }
 }
 
 impl Predicate<str> for StrFilePredicate {
     fn eval(&self, actual: &str) -> bool {
-        self.content == actual
+        self.content == self.normalize_variable(actual)
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
+        self.find_content_case(expected, variable)
     }
 }
 
@@ -176,3 +516,147 @@ impl fmt::Display for StrFilePredicate {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scratch(name: &str, content: &str) -> path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("predicates-fs-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// Drop the ANSI styling that the `color` feature adds to products.
+    fn strip_ansi(styled: &str) -> String {
+        let mut plain = String::new();
+        let mut rest = styled;
+        while let Some(start) = rest.find('\x1b') {
+            plain.push_str(&rest[..start]);
+            rest = &rest[start..];
+            rest = &rest[rest.find('m').map(|i| i + 1).unwrap_or(rest.len())..];
+        }
+        plain.push_str(rest);
+        plain
+    }
+
+    fn product(case: &reflection::Case<'_>, name: &str) -> Option<String> {
+        case.products()
+            .find(|product| product.name() == name)
+            .map(|product| strip_ansi(&product.value().to_string()))
+    }
+
+    #[test]
+    #[cfg(feature = "diff")]
+    fn str_file_reports_diff() {
+        let path = scratch("str_file_reports_diff", "a\nb\n");
+        let p = eq_file(&path).utf8().unwrap();
+        let case = p.find_case(false, "a\nc\n").unwrap();
+        assert_eq!(
+            product(&case, "diff").unwrap(),
+            format!(
+                "\n--- \t{}\n+++ \tvar\n@@ -2 +2 @@\n-b\n+c\n",
+                path.display()
+            )
+        );
+        assert!(p.find_case(false, "a\nb\n").is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "diff"))]
+    fn str_file_reports_var() {
+        let path = scratch("str_file_reports_var", "a\nb\n");
+        let p = eq_file(&path).utf8().unwrap();
+        let case = p.find_case(false, "a\nc\n").unwrap();
+        assert_eq!(product(&case, "var").unwrap(), "a\nc\n");
+    }
+
+    #[test]
+    #[cfg(feature = "normalize-line-endings")]
+    fn binary_file_normalize_newlines() {
+        let path = scratch("binary_file_normalize_newlines", "a\r\nb");
+        let p = eq_file(&path);
+        assert!(!Predicate::<[u8]>::eval(&p, b"a\nb"));
+
+        let p = p.normalize_newlines();
+        assert!(Predicate::<[u8]>::eval(&p, b"a\nb"));
+        assert!(Predicate::<[u8]>::eval(&p, b"a\r\nb"));
+        assert!(Predicate::<[u8]>::eval(&p, b"a\rb"));
+        assert!(!Predicate::<[u8]>::eval(&p, b"a\n\nb"));
+    }
+
+    #[test]
+    #[cfg(feature = "normalize-line-endings")]
+    fn str_file_normalize_newlines() {
+        let path = scratch("str_file_normalize_newlines", "a\r\nb\r\n");
+        let p = eq_file(&path).utf8().unwrap();
+        assert!(!Predicate::<str>::eval(&p, "a\nb\n"));
+
+        let p = p.normalize_newlines();
+        assert!(Predicate::<str>::eval(&p, "a\nb\n"));
+        assert!(Predicate::<str>::eval(&p, "a\r\nb\r\n"));
+        assert!(Predicate::<str>::eval(&p, "a\rb\r"));
+        assert!(!Predicate::<str>::eval(&p, "a\nb"));
+        assert!(p.find_case(true, "a\nb\n").is_some());
+    }
+
+    fn missing(name: &str) -> path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("predicates-fs-{}-{name}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn lazy_file_reads_on_first_use() {
+        let path = missing("lazy_file_reads_on_first_use");
+        let p = eq_file_lazy(&path);
+        fs::write(&path, "a").unwrap();
+        assert!(Predicate::<[u8]>::eval(&p, b"a"));
+
+        fs::write(&path, "b").unwrap();
+        assert!(Predicate::<[u8]>::eval(&p, b"a"));
+    }
+
+    #[test]
+    fn lazy_file_caches_error() {
+        let path = missing("lazy_file_caches_error");
+        let p = eq_file_lazy(&path);
+        assert!(!Predicate::<[u8]>::eval(&p, b"a"));
+
+        fs::write(&path, "a").unwrap();
+        assert!(!Predicate::<[u8]>::eval(&p, b"a"));
+        assert!(p.find_case(true, b"a".as_slice()).is_none());
+    }
+
+    #[test]
+    fn lazy_file_reports_expected_file() {
+        let path = missing("lazy_file_reports_expected_file");
+        let p = eq_file_lazy(&path);
+        let case = p.find_case(false, b"a".as_slice()).unwrap();
+        assert_eq!(
+            product(&case, "expected file").unwrap(),
+            path.display().to_string()
+        );
+        assert!(product(&case, "error").is_some());
+    }
+
+    #[test]
+    fn lazy_file_reports_expected_file_when_var_unreadable() {
+        let path = missing("lazy_file_reports_expected_file_when_var_unreadable");
+        let variable = missing("lazy_file_reports_expected_file_when_var_unreadable-var");
+        let p = eq_file_lazy(&path);
+        let case = p.find_case(false, variable.as_path()).unwrap();
+        assert_eq!(
+            product(&case, "var").unwrap(),
+            variable.display().to_string()
+        );
+        assert!(product(&case, "error").is_some());
+        assert_eq!(
+            product(&case, "expected file").unwrap(),
+            path.display().to_string()
+        );
+        assert!(product(&case, "expected file error").is_some());
+    }
+}
@@ -0,0 +1,610 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+use std::fs;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+use std::path;
+
+use crate::Predicate;
+use crate::reflection;
+
+/// Permission bits of `st_mode`, without the file type.
+#[cfg(unix)]
+const PERMISSION_BITS: u32 = 0o7777;
+
+/// Apply `p` to a value read from `variable`'s metadata, reporting the value as `name`.
+fn find_metadata_case<'a, P, T>(
+    predicate: &'a dyn reflection::PredicateReflection,
+    p: &'a P,
+    expected: bool,
+    variable: &path::Path,
+    name: &'static str,
+    get: impl FnOnce(&fs::Metadata) -> T,
+    show: impl FnOnce(&T) -> String,
+) -> Option<reflection::Case<'a>>
+where
+    P: Predicate<T>,
+{
+    match (expected, variable.metadata()) {
+        (_, Ok(metadata)) => {
+            let actual = get(&metadata);
+            p.find_case(expected, &actual).map(|child| {
+                reflection::Case::new(Some(predicate), child.result())
+                    .add_product(reflection::Product::new(name, show(&actual)))
+                    .add_child(child)
+            })
+        }
+        (true, Err(_)) => None,
+        (false, Err(err)) => Some(
+            reflection::Case::new(Some(predicate), false)
+                .add_product(reflection::Product::new("error", err)),
+        ),
+    }
+}
+
+/// Describe the permissions of `metadata`, as the mode on unix.
+fn permissions(metadata: &fs::Metadata) -> String {
+    #[cfg(unix)]
+    {
+        format!("{:#o}", metadata.mode() & PERMISSION_BITS)
+    }
+    #[cfg(not(unix))]
+    {
+        format!("readonly: {}", metadata.permissions().readonly())
+    }
+}
+
+/// Predicate that checks the size of a file.
+///
+/// This is created by the `predicate::path::size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizePredicate<P>
+where
+    P: Predicate<u64>,
+{
+    p: P,
+}
+
+impl<P> Predicate<path::Path> for SizePredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| self.p.eval(&m.len()))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        find_metadata_case(
+            self,
+            &self.p,
+            expected,
+            variable,
+            "actual size",
+            fs::Metadata::len,
+            u64::to_string,
+        )
+    }
+}
+
+impl<P> reflection::PredicateReflection for SizePredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for SizePredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("size")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the size of the file, in bytes.
+///
+/// Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::size(predicate::eq(12));
+/// assert_eq!(true, predicate_fn.eval(Path::new("tests/hello_world")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("tests/empty_file")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+pub fn size<P>(p: P) -> SizePredicate<P>
+where
+    P: Predicate<u64>,
+{
+    SizePredicate { p }
+}
+
+/// Predicate that checks whether a path is read-only.
+///
+/// This is created by the `predicate::path::is_readonly`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadonlyPredicate {}
+
+impl Predicate<path::Path> for ReadonlyPredicate {
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| m.permissions().readonly())
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        match (expected, variable.metadata()) {
+            (_, Ok(metadata)) => {
+                let result = metadata.permissions().readonly();
+                (result == expected).then(|| {
+                    reflection::Case::new(Some(self), result).add_product(reflection::Product::new(
+                        "actual permissions",
+                        permissions(&metadata),
+                    ))
+                })
+            }
+            (true, Err(_)) => None,
+            (false, Err(err)) => Some(
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("error", err)),
+            ),
+        }
+    }
+}
+
+impl reflection::PredicateReflection for ReadonlyPredicate {}
+
+impl fmt::Display for ReadonlyPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("is_readonly")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures the path is read-only.
+///
+/// This checks the permissions, as [`std::fs::Permissions::readonly`] does, rather than whether
+/// the current user could write to the path.  Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::is_readonly();
+/// assert_eq!(false, predicate_fn.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+pub fn is_readonly() -> ReadonlyPredicate {
+    ReadonlyPredicate {}
+}
+
+/// Predicate that checks whether any execute bit is set.
+///
+/// This is created by the `predicate::path::is_executable`.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutablePredicate {}
+
+#[cfg(unix)]
+impl Predicate<path::Path> for ExecutablePredicate {
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| m.mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        match (expected, variable.metadata()) {
+            (_, Ok(metadata)) => {
+                let result = metadata.mode() & 0o111 != 0;
+                (result == expected).then(|| {
+                    reflection::Case::new(Some(self), result).add_product(reflection::Product::new(
+                        "actual mode",
+                        permissions(&metadata),
+                    ))
+                })
+            }
+            (true, Err(_)) => None,
+            (false, Err(err)) => Some(
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("error", err)),
+            ),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl reflection::PredicateReflection for ExecutablePredicate {}
+
+#[cfg(unix)]
+impl fmt::Display for ExecutablePredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("is_executable")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures the path has an execute bit set for the owner, group,
+/// or others.
+///
+/// Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::is_executable();
+/// assert_eq!(false, predicate_fn.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+#[cfg(unix)]
+pub fn is_executable() -> ExecutablePredicate {
+    ExecutablePredicate {}
+}
+
+/// Predicate that checks the permission bits of a path.
+///
+/// This is created by the `predicate::path::mode`.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModePredicate<P>
+where
+    P: Predicate<u32>,
+{
+    p: P,
+}
+
+#[cfg(unix)]
+impl<P> Predicate<path::Path> for ModePredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| self.p.eval(&(m.mode() & PERMISSION_BITS)))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        find_metadata_case(
+            self,
+            &self.p,
+            expected,
+            variable,
+            "actual mode",
+            |m| m.mode() & PERMISSION_BITS,
+            |mode| format!("{mode:#o}"),
+        )
+    }
+}
+
+#[cfg(unix)]
+impl<P> reflection::PredicateReflection for ModePredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+#[cfg(unix)]
+impl<P> fmt::Display for ModePredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("mode")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the permission bits of the path, like `0o644`.
+///
+/// The file type bits of `st_mode` are masked off.  Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::mode(predicate::function(|mode: &u32| mode & 0o400 != 0));
+/// assert_eq!(true, predicate_fn.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+#[cfg(unix)]
+pub fn mode<P>(p: P) -> ModePredicate<P>
+where
+    P: Predicate<u32>,
+{
+    ModePredicate { p }
+}
+
+/// Predicate that checks the owner of a path.
+///
+/// This is created by the `predicate::path::owner_uid`.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OwnerUidPredicate<P>
+where
+    P: Predicate<u32>,
+{
+    p: P,
+}
+
+#[cfg(unix)]
+impl<P> Predicate<path::Path> for OwnerUidPredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| self.p.eval(&m.uid()))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        find_metadata_case(
+            self,
+            &self.p,
+            expected,
+            variable,
+            "actual uid",
+            MetadataExt::uid,
+            u32::to_string,
+        )
+    }
+}
+
+#[cfg(unix)]
+impl<P> reflection::PredicateReflection for OwnerUidPredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+#[cfg(unix)]
+impl<P> fmt::Display for OwnerUidPredicate<P>
+where
+    P: Predicate<u32>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("owner_uid")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the user ID of the path's owner.
+///
+/// Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::os::unix::fs::MetadataExt;
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let uid = std::fs::metadata("Cargo.toml").unwrap().uid();
+/// let predicate_fn = predicate::path::owner_uid(predicate::eq(uid));
+/// assert_eq!(true, predicate_fn.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+#[cfg(unix)]
+pub fn owner_uid<P>(p: P) -> OwnerUidPredicate<P>
+where
+    P: Predicate<u32>,
+{
+    OwnerUidPredicate { p }
+}
+
+/// Predicate that checks the number of hard links to a path.
+///
+/// This is created by the `predicate::path::nlink`.
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NlinkPredicate<P>
+where
+    P: Predicate<u64>,
+{
+    p: P,
+}
+
+#[cfg(unix)]
+impl<P> Predicate<path::Path> for NlinkPredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn eval(&self, path: &path::Path) -> bool {
+        path.metadata()
+            .map(|m| self.p.eval(&m.nlink()))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        find_metadata_case(
+            self,
+            &self.p,
+            expected,
+            variable,
+            "actual nlink",
+            MetadataExt::nlink,
+            u64::to_string,
+        )
+    }
+}
+
+#[cfg(unix)]
+impl<P> reflection::PredicateReflection for NlinkPredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+#[cfg(unix)]
+impl<P> fmt::Display for NlinkPredicate<P>
+where
+    P: Predicate<u64>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("nlink")
+        )
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the number of hard links to the path.
+///
+/// Symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::nlink(predicate::ge(1));
+/// assert_eq!(true, predicate_fn.eval(Path::new("Cargo.toml")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("non-existent-file.foo")));
+/// ```
+#[cfg(unix)]
+pub fn nlink<P>(p: P) -> NlinkPredicate<P>
+where
+    P: Predicate<u64>,
+{
+    NlinkPredicate { p }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn product(case: &reflection::Case<'_>, name: &str) -> String {
+        case.products()
+            .find(|product| product.name() == name)
+            .map(|product| product.value().to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn size_reports_actual() {
+        let p = size(crate::ord::eq(0));
+        let case = p
+            .find_case(false, path::Path::new("tests/hello_world"))
+            .unwrap();
+        assert_eq!(product(&case, "actual size"), "12");
+        assert_eq!(case.children().count(), 1);
+
+        let case = p
+            .find_case(false, path::Path::new("non-existent-file.foo"))
+            .unwrap();
+        assert_eq!(case.products().next().unwrap().name(), "error");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn mode_reports_octal() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("predicates-mode-{}", std::process::id()));
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+
+        assert!(mode(crate::ord::eq(0o640)).eval(&path));
+        let p = mode(crate::ord::eq(0o644));
+        let case = p.find_case(false, &path).unwrap();
+        assert_eq!(product(&case, "actual mode"), "0o640");
+        let p = is_executable();
+        let case = p.find_case(false, &path).unwrap();
+        assert_eq!(product(&case, "actual mode"), "0o640");
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o750)).unwrap();
+        assert!(is_executable().eval(&path));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -14,7 +14,18 @@ mod existence;
 pub use self::existence::{ExistencePredicate, exists, missing};
 mod ft;
 pub use self::ft::{FileTypePredicate, is_dir, is_file, is_symlink};
+mod metadata;
+#[cfg(unix)]
+pub use self::metadata::{
+    ExecutablePredicate, ModePredicate, NlinkPredicate, OwnerUidPredicate, is_executable, mode,
+    nlink, owner_uid,
+};
+pub use self::metadata::{ReadonlyPredicate, SizePredicate, is_readonly, size};
 mod fc;
 pub use self::fc::{FileContentPredicate, PredicateFileContentExt};
 mod fs;
-pub use self::fs::{BinaryFilePredicate, StrFilePredicate, eq_file};
+pub use self::fs::{
+    BinaryFilePredicate, LazyFilePredicate, StrFilePredicate, eq_file, eq_file_lazy, try_eq_file,
+};
+pub(crate) mod snapshot;
+pub use self::snapshot::{FileSnapshotPredicate, snapshot};
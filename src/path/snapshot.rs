@@ -0,0 +1,358 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path;
+use std::sync;
+
+use crate::Predicate;
+use crate::hexdump;
+use crate::reflection;
+
+/// Environment variable that, when set to `1`, rewrites snapshots instead of failing.
+const UPDATE_VAR: &str = "PREDICATES_UPDATE";
+
+/// The outcome of comparing content to a snapshot.
+#[derive(Debug)]
+enum Comparison {
+    Matched,
+    /// The snapshot's content, which doesn't match; empty when an update will create it.
+    Mismatched(Vec<u8>),
+}
+
+/// Whether a predicate has rewritten its snapshot.
+///
+/// This is bookkeeping rather than configuration, so it doesn't take part in equality.
+#[derive(Debug, Default)]
+struct Updated(sync::atomic::AtomicBool);
+
+impl Updated {
+    fn get(&self) -> bool {
+        self.0.load(sync::atomic::Ordering::Relaxed)
+    }
+
+    fn set(&self) {
+        self.0.store(true, sync::atomic::Ordering::Relaxed);
+    }
+}
+
+impl Clone for Updated {
+    fn clone(&self) -> Self {
+        Self(sync::atomic::AtomicBool::new(self.get()))
+    }
+}
+
+impl PartialEq for Updated {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Updated {}
+
+/// Predicate that compares content against a golden file.
+///
+/// This is created by the `predicate::path::snapshot` and `predicate::str::matches_file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSnapshotPredicate {
+    path: path::PathBuf,
+    update: Option<bool>,
+    updated: Updated,
+}
+
+impl FileSnapshotPredicate {
+    /// Rewrite the snapshot when it doesn't match, rather than failing.
+    ///
+    /// By default, snapshots are rewritten when the `PREDICATES_UPDATE` environment variable is
+    /// set to `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::path::snapshot("tests/hello_world").update(false);
+    /// assert_eq!(true, predicate_fn.eval("Hello World!"));
+    /// assert_eq!(false, predicate_fn.eval("Goodbye World!"));
+    /// ```
+    pub fn update(mut self, yes: bool) -> Self {
+        self.update = Some(yes);
+        self
+    }
+
+    /// Whether evaluating the predicate has rewritten the snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let path = std::env::temp_dir().join("predicates-doc-updated");
+    /// let _ = std::fs::remove_file(&path);
+    /// let predicate_fn = predicate::path::snapshot(&path).update(true);
+    /// assert_eq!(false, predicate_fn.updated());
+    /// assert_eq!(true, predicate_fn.eval("Hello World!"));
+    /// assert_eq!(true, predicate_fn.updated());
+    /// ```
+    pub fn updated(&self) -> bool {
+        self.updated.get()
+    }
+
+    fn updating(&self) -> bool {
+        self.update
+            .unwrap_or_else(|| env::var_os(UPDATE_VAR).map(|v| v == "1").unwrap_or(false))
+    }
+
+    /// Compare `variable` to the snapshot, without touching it.
+    fn compare(&self, variable: &[u8]) -> io::Result<Comparison> {
+        match fs::read(&self.path) {
+            Ok(snapshot) if snapshot == variable => Ok(Comparison::Matched),
+            Ok(snapshot) => Ok(Comparison::Mismatched(snapshot)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && self.updating() => {
+                Ok(Comparison::Mismatched(Vec::new()))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Check `variable` against the snapshot, rewriting it if requested.
+    fn check(&self, variable: &[u8]) -> bool {
+        match self.compare(variable) {
+            Ok(Comparison::Matched) => true,
+            Ok(Comparison::Mismatched(_)) if self.updating() => self.write(variable).is_ok(),
+            _ => false,
+        }
+    }
+
+    fn write(&self, variable: &[u8]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, variable)?;
+        self.updated.set();
+        Ok(())
+    }
+
+    fn find_content_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &[u8],
+    ) -> Option<reflection::Case<'a>> {
+        let snapshot = match self.compare(variable) {
+            Ok(Comparison::Matched) => {
+                return expected.then(|| {
+                    let case = reflection::Case::new(Some(self), true);
+                    if self.updated() {
+                        case.add_product(reflection::Product::new(
+                            "updated",
+                            self.path.display().to_string(),
+                        ))
+                    } else {
+                        case
+                    }
+                });
+            }
+            Ok(Comparison::Mismatched(_)) if self.updating() => {
+                return expected.then(|| {
+                    reflection::Case::new(Some(self), true).add_product(reflection::Product::new(
+                        "update",
+                        format!("{} is rewritten on eval", self.path.display()),
+                    ))
+                });
+            }
+            Ok(Comparison::Mismatched(snapshot)) => snapshot,
+            Err(err) => {
+                return (!expected).then(|| {
+                    reflection::Case::new(Some(self), false)
+                        .add_product(reflection::Product::new("error", err))
+                });
+            }
+        };
+        if expected {
+            return None;
+        }
+
+        let case = reflection::Case::new(Some(self), false);
+        let case = match (
+            std::str::from_utf8(&snapshot),
+            std::str::from_utf8(variable),
+        ) {
+            #[cfg(feature = "diff")]
+            (Ok(snapshot), Ok(variable)) => case.add_product(reflection::Product::new(
+                "diff",
+                crate::str::DiffOptions::default()
+                    .labels(self.path.display().to_string(), "var")
+                    .render(snapshot, variable),
+            )),
+            #[cfg(not(feature = "diff"))]
+            (Ok(_), Ok(variable)) => {
+                case.add_product(reflection::Product::new("var", variable.to_owned()))
+            }
+            _ => case.add_product(reflection::Product::new(
+                "diff",
+                hexdump::diff(&snapshot, variable),
+            )),
+        };
+        Some(case.add_product(reflection::Product::new(
+            "update",
+            format!("rerun with {UPDATE_VAR}=1 to accept the new content"),
+        )))
+    }
+}
+
+impl Predicate<str> for FileSnapshotPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.check(variable.as_bytes())
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        self.find_content_case(expected, variable.as_bytes())
+    }
+}
+
+impl Predicate<[u8]> for FileSnapshotPredicate {
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.check(variable)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        self.find_content_case(expected, variable)
+    }
+}
+
+impl Predicate<path::Path> for FileSnapshotPredicate {
+    fn eval(&self, path: &path::Path) -> bool {
+        fs::read(path)
+            .map(|content| self.check(&content))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &path::Path,
+    ) -> Option<reflection::Case<'a>> {
+        super::fs::find_path_case(self, expected, variable, fs::read(variable), |content| {
+            self.find_content_case(expected, &content)
+        })
+    }
+}
+
+impl reflection::PredicateReflection for FileSnapshotPredicate {}
+
+impl fmt::Display for FileSnapshotPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{} {} {}",
+            palette.var("var"),
+            palette.description("matches snapshot"),
+            palette.expected(self.path.display())
+        )
+    }
+}
+
+/// Creates a new `Predicate` that compares against the content of a golden file.
+///
+/// The file is read each time the predicate is evaluated.  When the `PREDICATES_UPDATE`
+/// environment variable is set to `1`, evaluating the predicate rewrites a mismatched or missing
+/// file with the actual content instead of failing; check
+/// [`updated`][FileSnapshotPredicate::updated] to report which snapshots changed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::path::snapshot("tests/hello_world").update(false);
+/// assert_eq!(true, predicate_fn.eval(Path::new("tests/hello_world")));
+/// assert_eq!(false, predicate_fn.eval(Path::new("tests/empty_file")));
+/// ```
+pub fn snapshot<P: Into<path::PathBuf>>(path: P) -> FileSnapshotPredicate {
+    FileSnapshotPredicate {
+        path: path.into(),
+        update: None,
+        updated: Updated::default(),
+    }
+}
+
+/// Creates a new `Predicate` that compares a string against the content of a golden file.
+///
+/// See [`predicate::path::snapshot`][crate::path::snapshot()] for how snapshots are updated.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::matches_file("tests/hello_world").update(false);
+/// assert_eq!(true, predicate_fn.eval("Hello World!"));
+/// assert_eq!(false, predicate_fn.eval("Hello World!\n"));
+/// ```
+pub fn matches_file<P: Into<path::PathBuf>>(path: P) -> FileSnapshotPredicate {
+    snapshot(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scratch(name: &str) -> path::PathBuf {
+        let dir = env::temp_dir().join(format!("predicates-snapshot-{}", std::process::id()));
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn update_writes_snapshot() {
+        let path = scratch("update_writes_snapshot");
+        let p = snapshot(&path).update(true);
+        assert!(!p.updated());
+        assert!(p.eval("first\n"));
+        assert!(p.updated());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+        assert!(p.eval("second\n"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+
+        let case = p.find_case(true, "second\n").unwrap();
+        let updated = case.products().next().unwrap();
+        assert_eq!(updated.name(), "updated");
+        assert_eq!(updated.value().to_string(), path.display().to_string());
+
+        let p = snapshot(&path).update(false);
+        assert!(p.eval("second\n"));
+        assert!(!p.eval("first\n"));
+        assert!(!p.updated());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+    }
+
+    #[test]
+    fn find_case_does_not_write() {
+        let path = scratch("find_case_does_not_write");
+        let p = snapshot(&path).update(true);
+        let case = p.find_case(true, "content").unwrap();
+        assert_eq!(case.products().next().unwrap().name(), "update");
+        assert!(!path.exists());
+        assert!(!p.updated());
+    }
+
+    #[test]
+    fn missing_snapshot_is_reported() {
+        let path = scratch("missing_snapshot_is_reported");
+        let p = snapshot(&path).update(false);
+        assert!(!p.eval("content"));
+        let case = p.find_case(false, "content").unwrap();
+        assert!(case.products().any(|product| product.name() == "error"));
+    }
+}
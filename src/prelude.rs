@@ -11,6 +11,9 @@
 pub use crate::Predicate;
 pub use crate::boolean::PredicateBooleanExt;
 pub use crate::boxed::PredicateBoxExt;
+pub use crate::bytes::PredicateBytesExt;
+#[cfg(feature = "serde")]
+pub use crate::json::PredicateValueExt;
 pub use crate::name::PredicateNameExt;
 pub use crate::path::PredicateFileContentExt;
 pub use crate::str::PredicateStrExt;
@@ -27,23 +30,78 @@ pub mod predicate {
     ///
     /// This module contains predicates specific to string handling.
     pub mod str {
+        pub use crate::str::glob;
         pub use crate::str::is_empty;
+        pub use crate::str::matches_file;
+        pub use crate::str::similar_to;
         pub use crate::str::{contains, ends_with, starts_with};
 
         #[cfg(feature = "diff")]
         pub use crate::str::diff;
+        #[cfg(feature = "diff")]
+        pub use crate::str::matches_snapshot;
 
         #[cfg(feature = "regex")]
         pub use crate::str::is_match;
+        #[cfg(feature = "regex")]
+        pub use crate::str::{matches_all, matches_any};
+    }
+
+    /// `[u8]` Predicate factories
+    ///
+    /// This module contains predicates specific to byte slices.
+    pub mod bytes {
+        pub use crate::bytes::eq;
+        pub use crate::bytes::is_empty;
+        pub use crate::bytes::{contains, ends_with, starts_with};
+
+        #[cfg(feature = "regex")]
+        pub use crate::bytes::is_match;
+    }
+
+    /// `OsStr` Predicate factories
+    ///
+    /// This module contains predicates that compare `OsStr`s without decoding them.
+    pub mod os_str {
+        pub use crate::os_str::eq;
+        pub use crate::os_str::{contains, starts_with};
     }
 
     /// `Path` Predicate factories
     ///
     /// This module contains predicates specific to path handling.
     pub mod path {
-        pub use crate::path::eq_file;
+        pub use crate::path::snapshot;
+        pub use crate::path::{eq_file, eq_file_lazy, try_eq_file};
         pub use crate::path::{exists, missing};
         pub use crate::path::{is_dir, is_file, is_symlink};
+        #[cfg(unix)]
+        pub use crate::path::{is_executable, mode, nlink, owner_uid};
+        pub use crate::path::{is_readonly, size};
+    }
+
+    /// JSON Predicate factories
+    ///
+    /// This module contains predicates that compare JSON documents by structure.
+    #[cfg(feature = "json")]
+    pub mod json {
+        pub use crate::json::{eq, is_subset_of, pointer};
+    }
+
+    /// TOML Predicate factories
+    ///
+    /// This module contains predicates that compare TOML documents by structure.
+    #[cfg(feature = "toml")]
+    pub mod toml {
+        pub use crate::toml::{eq, is_subset_of, path};
+    }
+
+    /// YAML Predicate factories
+    ///
+    /// This module contains predicates that compare YAML documents by structure.
+    #[cfg(feature = "yaml")]
+    pub mod yaml {
+        pub use crate::yaml::{eq, is_subset_of, path};
     }
 
     /// `f64` Predicate factories
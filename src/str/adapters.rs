@@ -6,14 +6,17 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::borrow;
 use std::ffi;
 use std::fmt;
 use std::str;
 
 use crate::Predicate;
 use crate::reflection;
+use crate::str::encoding::{DecodePredicate, Encoding};
 #[cfg(feature = "normalize-line-endings")]
 use crate::str::normalize::NormalizedPredicate;
+use crate::str::redact::{RedactPredicate, Redaction};
 
 /// Predicate adapter that trims the variable being tested.
 ///
@@ -140,6 +143,108 @@ where
     }
 }
 
+/// Decode `variable`, replacing invalid UTF-8 with U+FFFD.
+///
+/// Also returns the byte ranges that were replaced.
+fn decode_lossy(variable: &[u8]) -> (borrow::Cow<'_, str>, Vec<String>) {
+    let mut replaced = Vec::new();
+    let mut offset = 0;
+    for chunk in variable.utf8_chunks() {
+        offset += chunk.valid().len();
+        let invalid = chunk.invalid().len();
+        if 0 < invalid {
+            replaced.push(format!("0x{:x}..0x{:x}", offset, offset + invalid));
+            offset += invalid;
+        }
+    }
+    (String::from_utf8_lossy(variable), replaced)
+}
+
+/// Predicate adapter that lossily converts a `str` predicate to byte predicate.
+///
+/// This is created by `pred.from_utf8_lossy()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    p: P,
+}
+
+impl<P> Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn find_bytes_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &[u8],
+    ) -> Option<reflection::Case<'a>> {
+        let (var_str, replaced) = decode_lossy(variable);
+        self.p.find_case(expected, &var_str).map(|child| {
+            let child =
+                child.add_product(reflection::Product::new("var as str", var_str.into_owned()));
+            if replaced.is_empty() {
+                child
+            } else {
+                child.add_product(reflection::Product::new(
+                    "replaced bytes",
+                    replaced.join(", "),
+                ))
+            }
+        })
+    }
+}
+
+impl<P> Predicate<ffi::OsStr> for Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn eval(&self, variable: &ffi::OsStr) -> bool {
+        self.p.eval(&decode_lossy(variable.as_encoded_bytes()).0)
+    }
+
+    fn find_case<'a>(
+        &'a self,
+        expected: bool,
+        variable: &ffi::OsStr,
+    ) -> Option<reflection::Case<'a>> {
+        self.find_bytes_case(expected, variable.as_encoded_bytes())
+    }
+}
+
+impl<P> Predicate<[u8]> for Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.p.eval(&String::from_utf8_lossy(variable))
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        self.find_bytes_case(expected, variable)
+    }
+}
+
+impl<P> reflection::PredicateReflection for Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for Utf8LossyPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
 /// `Predicate` extension adapting a `str` Predicate.
 pub trait PredicateStrExt
 where
@@ -180,6 +285,119 @@ where
         Utf8Predicate { p: self }
     }
 
+    /// Returns a `Utf8LossyPredicate` that adapts `Self` to a `[u8]` `Predicate`, replacing
+    /// invalid UTF-8 with U+FFFD.
+    ///
+    /// The byte ranges that were replaced are reported on failure.  On Windows, an `OsStr` that
+    /// isn't valid Unicode may have each of its unpaired surrogates replaced by several U+FFFD.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::starts_with("Hello").from_utf8_lossy();
+    /// let variable: &[u8] = b"Hello \xFF World";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// let variable: &[u8] = b"\xFFHello";
+    /// assert_eq!(false, predicate_fn.eval(variable));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_utf8_lossy(self) -> Utf8LossyPredicate<Self> {
+        Utf8LossyPredicate { p: self }
+    }
+
+    /// Returns a `DecodePredicate` that adapts `Self` to a little-endian UTF-16 `[u8]`
+    /// `Predicate`.
+    ///
+    /// A leading byte order mark is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::eq("Hi").from_utf16le();
+    /// let variable: &[u8] = b"\xFF\xFEH\x00i\x00";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// let variable: &[u8] = b"H\x00i";
+    /// assert_eq!(false, predicate_fn.eval(variable));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_utf16le(self) -> DecodePredicate<Self> {
+        DecodePredicate {
+            p: self,
+            encoding: Encoding::Utf16Le,
+        }
+    }
+
+    /// Returns a `DecodePredicate` that adapts `Self` to a big-endian UTF-16 `[u8]` `Predicate`.
+    ///
+    /// A leading byte order mark is skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::eq("Hi").from_utf16be();
+    /// let variable: &[u8] = b"\x00H\x00i";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_utf16be(self) -> DecodePredicate<Self> {
+        DecodePredicate {
+            p: self,
+            encoding: Encoding::Utf16Be,
+        }
+    }
+
+    /// Returns a `DecodePredicate` that adapts `Self` to a Latin-1 (ISO-8859-1) `[u8]`
+    /// `Predicate`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::eq("café").from_latin1();
+    /// let variable: &[u8] = b"caf\xE9";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_latin1(self) -> DecodePredicate<Self> {
+        DecodePredicate {
+            p: self,
+            encoding: Encoding::Latin1,
+        }
+    }
+
+    /// Returns a `DecodePredicate` that adapts `Self` to a `[u8]` `Predicate`, decoding UTF-8 or
+    /// UTF-16 as identified by a byte order mark.
+    ///
+    /// Without a byte order mark, the data is decoded as UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::eq("Hi").from_text();
+    /// let variable: &[u8] = b"\xFF\xFEH\x00i\x00";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// let variable: &[u8] = b"\xEF\xBB\xBFHi";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// let variable: &[u8] = b"Hi";
+    /// assert_eq!(true, predicate_fn.eval(variable));
+    /// ```
+    #[allow(clippy::wrong_self_convention)]
+    fn from_text(self) -> DecodePredicate<Self> {
+        DecodePredicate {
+            p: self,
+            encoding: Encoding::Text,
+        }
+    }
+
     /// Returns a `NormalizedPredicate` that ensures
     ///  the newlines within the data passed to `Self` is normalised.
     ///
@@ -199,6 +417,65 @@ where
     fn normalize(self) -> NormalizedPredicate<Self> {
         NormalizedPredicate { p: self }
     }
+
+    /// Returns a `RedactPredicate` that rewrites volatile parts of the data passed to `Self`.
+    ///
+    /// The redactions are applied in order, and are reported as parameters of the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    /// use predicates::str::Redaction;
+    ///
+    /// let predicate_fn = predicate::eq("Wrote [ROOT]/out.txt").redact([
+    ///     Redaction::literal("/tmp/abc123", "[ROOT]"),
+    ///     Redaction::literal("C:\\Temp\\abc123", "[ROOT]"),
+    /// ]);
+    /// assert_eq!(true, predicate_fn.eval("Wrote /tmp/abc123/out.txt"));
+    /// assert_eq!(true, predicate_fn.eval("Wrote C:\\Temp\\abc123/out.txt"));
+    /// assert_eq!(false, predicate_fn.eval("Wrote /tmp/xyz789/out.txt"));
+    /// ```
+    fn redact<I>(self, redactions: I) -> RedactPredicate<Self>
+    where
+        I: IntoIterator<Item = Redaction>,
+    {
+        RedactPredicate {
+            p: self,
+            redactions: redactions.into_iter().collect(),
+        }
+    }
 }
 
 impl<P> PredicateStrExt for P where P: Predicate<str> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode_lossy_reports_replacements() {
+        let (decoded, replaced) = decode_lossy(b"a\xFFb\xE2\x82c");
+        assert_eq!(decoded, "a\u{FFFD}b\u{FFFD}c");
+        assert_eq!(replaced, ["0x1..0x2", "0x3..0x5"]);
+    }
+
+    #[test]
+    fn lossy_reports_replaced_bytes() {
+        let p = crate::str::starts_with("Hello").from_utf8_lossy();
+        let variable: &[u8] = b"\xFFHello\xE2\x82";
+        let case = p.find_case(false, variable).unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect();
+        assert_eq!(
+            products[products.len() - 2..],
+            [
+                ("var as str".to_owned(), "\u{FFFD}Hello\u{FFFD}".to_owned()),
+                ("replaced bytes".to_owned(), "0x0..0x1, 0x6..0x8".to_owned()),
+            ]
+        );
+        assert!(p.find_case(false, b"Hello".as_slice()).is_none());
+    }
+}
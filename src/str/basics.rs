@@ -10,6 +10,7 @@ use std::fmt;
 
 use crate::Predicate;
 use crate::reflection;
+use crate::str::locate;
 use crate::utils;
 
 /// Predicate that checks for empty strings.
@@ -72,8 +73,16 @@ impl Predicate<str> for StartsWithPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
-            .map(|case| case.add_product(reflection::Product::new("var", variable.to_owned())))
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let case = case.add_product(reflection::Product::new("var", variable.to_owned()));
+            if case.result() {
+                return case;
+            }
+            match locate::closest_match(variable, &self.pattern, locate::Anchor::Start) {
+                Some(product) => case.add_product(product),
+                None => case,
+            }
+        })
     }
 }
 
@@ -126,8 +135,16 @@ impl Predicate<str> for EndsWithPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
-            .map(|case| case.add_product(reflection::Product::new("var", variable.to_owned())))
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let case = case.add_product(reflection::Product::new("var", variable.to_owned()));
+            if case.result() {
+                return case;
+            }
+            match locate::closest_match(variable, &self.pattern, locate::Anchor::End) {
+                Some(product) => case.add_product(product),
+                None => case,
+            }
+        })
     }
 }
 
@@ -200,8 +217,26 @@ impl Predicate<str> for ContainsPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
-            .map(|case| case.add_product(reflection::Product::new("var", variable.to_owned())))
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let spans: Vec<_> = variable
+                .match_indices(&self.pattern)
+                .map(|(i, m)| i..i + m.len())
+                .collect();
+            let case = case.add_product(reflection::Product::new(
+                "var",
+                locate::highlight(variable, &spans),
+            ));
+            if !case.result() {
+                if let Some(product) =
+                    locate::closest_match(variable, &self.pattern, locate::Anchor::Anywhere)
+                {
+                    return case.add_product(product);
+                }
+            }
+            locate::locations(variable, &spans)
+                .into_iter()
+                .fold(case, |case, product| case.add_product(product))
+        })
     }
 }
 
@@ -235,13 +270,23 @@ impl Predicate<str> for MatchesPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        let actual_count = variable.matches(&self.pattern).count();
+        let spans: Vec<_> = variable
+            .match_indices(&self.pattern)
+            .map(|(i, m)| i..i + m.len())
+            .collect();
+        let actual_count = spans.len();
         let result = self.count == actual_count;
         if result == expected {
+            let case = reflection::Case::new(Some(self), result)
+                .add_product(reflection::Product::new(
+                    "var",
+                    locate::highlight(variable, &spans),
+                ))
+                .add_product(reflection::Product::new("actual count", actual_count));
             Some(
-                reflection::Case::new(Some(self), result)
-                    .add_product(reflection::Product::new("var", variable.to_owned()))
-                    .add_product(reflection::Product::new("actual count", actual_count)),
+                locate::locations(variable, &spans)
+                    .into_iter()
+                    .fold(case, |case, product| case.add_product(product)),
             )
         } else {
             None
@@ -9,15 +9,397 @@
 use std::borrow;
 use std::fmt;
 
+use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
+
 use crate::Predicate;
+use crate::distance;
 use crate::reflection;
 
+/// Upper bound on the table size used to align the tokens of two lines.
+const MAX_ALIGNMENT_CELLS: usize = 1 << 22;
+/// Narrowest column a side-by-side diff will shrink to.
+const MIN_COLUMN_WIDTH: usize = 10;
+const TAB_WIDTH: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffMode {
+    Lines,
+    Words,
+    Chars,
+    SideBySide(usize),
+}
+
+/// How a diff between two strings is rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DiffOptions {
+    mode: DiffMode,
+    context: usize,
+    labels: (borrow::Cow<'static, str>, borrow::Cow<'static, str>),
+    max_lines: Option<usize>,
+    show_whitespace: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            mode: DiffMode::Lines,
+            context: 0,
+            labels: ("orig".into(), "var".into()),
+            max_lines: None,
+            show_whitespace: false,
+        }
+    }
+}
+
+impl DiffOptions {
+    pub(crate) fn by_words(mut self) -> Self {
+        self.mode = DiffMode::Words;
+        self
+    }
+
+    pub(crate) fn by_chars(mut self) -> Self {
+        self.mode = DiffMode::Chars;
+        self
+    }
+
+    pub(crate) fn side_by_side(mut self, width: usize) -> Self {
+        self.mode = DiffMode::SideBySide(width);
+        self
+    }
+
+    pub(crate) fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+
+    pub(crate) fn labels<E, A>(mut self, expected: E, actual: A) -> Self
+    where
+        E: Into<borrow::Cow<'static, str>>,
+        A: Into<borrow::Cow<'static, str>>,
+    {
+        self.labels = (expected.into(), actual.into());
+        self
+    }
+
+    pub(crate) fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines);
+        self
+    }
+
+    pub(crate) fn show_whitespace(mut self) -> Self {
+        self.show_whitespace = true;
+        self
+    }
+
+    /// Render a diff of `variable` against `orig`, suitable for a `diff` product.
+    pub(crate) fn render(&self, orig: &str, variable: &str) -> String {
+        let palette = crate::Palette::new(true);
+        let (orig, variable) = if self.show_whitespace {
+            (
+                borrow::Cow::Owned(show_whitespace(orig)),
+                borrow::Cow::Owned(show_whitespace(variable)),
+            )
+        } else {
+            (borrow::Cow::Borrowed(orig), borrow::Cow::Borrowed(variable))
+        };
+
+        let mut diff = match self.mode {
+            DiffMode::Lines => self.render_unified(&orig, &variable, palette),
+            DiffMode::Words | DiffMode::Chars => self.render_inline(&orig, &variable, palette),
+            DiffMode::SideBySide(width) => {
+                self.render_side_by_side(&orig, &variable, width, palette)
+            }
+        };
+        if let Some(max_lines) = self.max_lines {
+            if max_lines < diff.len() {
+                let omitted = diff.len() - max_lines;
+                diff.truncate(max_lines);
+                diff.push(format!(
+                    "{:#}\n",
+                    palette.description(format_args!("... {omitted} more lines truncated"))
+                ));
+            }
+        }
+        diff.insert(0, "\n".to_owned());
+        diff.join("")
+    }
+
+    fn render_unified(&self, orig: &str, variable: &str, palette: crate::Palette) -> Vec<String> {
+        let orig: Vec<_> = orig.lines().map(|l| format!("{l}\n")).collect();
+        let variable: Vec<_> = variable.lines().map(|l| format!("{l}\n")).collect();
+        let diff = difflib::unified_diff(
+            &orig,
+            &variable,
+            "",
+            "",
+            &palette.expected(&self.labels.0).to_string(),
+            &palette.var(&self.labels.1).to_string(),
+            self.context,
+        );
+        colorize_diff(diff, palette)
+    }
+
+    /// Render a diff that highlights the changes within each changed line.
+    fn render_inline(&self, orig: &str, variable: &str, palette: crate::Palette) -> Vec<String> {
+        let orig: Vec<_> = orig.lines().collect();
+        let variable: Vec<_> = variable.lines().collect();
+
+        let mut diff = Vec::new();
+        let mut matcher = difflib::sequencematcher::SequenceMatcher::new(&orig, &variable);
+        for group in matcher.get_grouped_opcodes(self.context) {
+            if diff.is_empty() {
+                diff.push(format!(
+                    "{:#} {:#}\n",
+                    palette.expected("---"),
+                    palette.expected(&self.labels.0)
+                ));
+                diff.push(format!(
+                    "{:#} {:#}\n",
+                    palette.var("+++"),
+                    palette.var(&self.labels.1)
+                ));
+            }
+            let (first, last) = (&group[0], &group[group.len() - 1]);
+            diff.push(format!(
+                "{:#}\n",
+                palette.description(format_args!(
+                    "@@ -{} +{} @@",
+                    format_range(first.first_start, last.first_end),
+                    format_range(first.second_start, last.second_end),
+                ))
+            ));
+            for op in &group {
+                let removed = &orig[op.first_start..op.first_end];
+                let added = &variable[op.second_start..op.second_end];
+                if op.tag == "equal" {
+                    diff.extend(added.iter().map(|line| format!(" {line}\n")));
+                    continue;
+                }
+                let (mut removed_lines, mut added_lines) = (Vec::new(), Vec::new());
+                for i in 0..removed.len().max(added.len()) {
+                    match (removed.get(i), added.get(i)) {
+                        (Some(removed), Some(added)) => {
+                            let (removed, added) =
+                                highlight_line(removed, added, self.mode, palette);
+                            removed_lines.push(removed);
+                            added_lines.push(added);
+                        }
+                        (Some(removed), None) => {
+                            removed_lines.push(distance::mark_removed(removed, palette));
+                        }
+                        (None, Some(added)) => {
+                            added_lines.push(distance::mark_added(added, palette));
+                        }
+                        (None, None) => unreachable!("index is bounded by the longer side"),
+                    }
+                }
+                diff.extend(
+                    removed_lines
+                        .iter()
+                        .map(|line| format!("{:#}{line}\n", palette.expected("-"))),
+                );
+                diff.extend(
+                    added_lines
+                        .iter()
+                        .map(|line| format!("{:#}{line}\n", palette.var("+"))),
+                );
+            }
+        }
+        diff
+    }
+
+    /// Render a diff with the two sides in adjacent columns.
+    fn render_side_by_side(
+        &self,
+        orig: &str,
+        variable: &str,
+        width: usize,
+        palette: crate::Palette,
+    ) -> Vec<String> {
+        let column = (width.saturating_sub(3) / 2).max(MIN_COLUMN_WIDTH);
+        let orig: Vec<_> = orig.lines().collect();
+        let variable: Vec<_> = variable.lines().collect();
+
+        let mut diff = Vec::new();
+        let mut matcher = difflib::sequencematcher::SequenceMatcher::new(&orig, &variable);
+        for group in matcher.get_grouped_opcodes(self.context) {
+            if diff.is_empty() {
+                diff.extend(side_by_side_rows(
+                    Some(&self.labels.0),
+                    '|',
+                    Some(&self.labels.1),
+                    column,
+                    palette,
+                ));
+            }
+            let (first, last) = (&group[0], &group[group.len() - 1]);
+            diff.push(format!(
+                "{:#}\n",
+                palette.description(format_args!(
+                    "@@ -{} +{} @@",
+                    format_range(first.first_start, last.first_end),
+                    format_range(first.second_start, last.second_end),
+                ))
+            ));
+            for op in &group {
+                let removed = &orig[op.first_start..op.first_end];
+                let added = &variable[op.second_start..op.second_end];
+                for i in 0..removed.len().max(added.len()) {
+                    let (left, right) = (removed.get(i).copied(), added.get(i).copied());
+                    let gutter = match (op.tag.as_str(), left, right) {
+                        ("equal", _, _) => ' ',
+                        (_, Some(_), Some(_)) => '|',
+                        (_, Some(_), None) => '<',
+                        (_, None, _) => '>',
+                    };
+                    diff.extend(side_by_side_rows(left, gutter, right, column, palette));
+                }
+            }
+        }
+        diff
+    }
+}
+
 /// Predicate that diffs two strings.
 ///
 /// This is created by the `predicate::str::diff`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DifferencePredicate {
     orig: borrow::Cow<'static, str>,
+    options: DiffOptions,
+}
+
+impl DifferencePredicate {
+    /// Highlight the words that changed within each line.
+    ///
+    /// Each changed line is shown as a `-` line and a `+` line, like the default line diff.
+    /// Without the `color` feature, removed words are marked as `[-word-]` and added words as
+    /// `{+word+}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").by_words();
+    /// assert_eq!(true, predicate_fn.eval("Hello World"));
+    /// assert_eq!(false, predicate_fn.eval("Hello Moon"));
+    /// ```
+    pub fn by_words(mut self) -> Self {
+        self.options = self.options.by_words();
+        self
+    }
+
+    /// Highlight the characters that changed within each line.
+    ///
+    /// Each changed line is shown as a `-` line and a `+` line, like the default line diff.
+    /// Without the `color` feature, removed characters are marked as `[-c-]` and added
+    /// characters as `{+c+}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").by_chars();
+    /// assert_eq!(true, predicate_fn.eval("Hello World"));
+    /// assert_eq!(false, predicate_fn.eval("Hello Word"));
+    /// ```
+    pub fn by_chars(mut self) -> Self {
+        self.options = self.options.by_chars();
+        self
+    }
+
+    /// Lay out the original and the variable in adjacent columns.
+    ///
+    /// The diff fits within `width` characters, wrapping long lines.  Rows are marked `|` when
+    /// changed, `<` when only in the original, and `>` when only in the variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").side_by_side(80);
+    /// assert_eq!(true, predicate_fn.eval("Hello World"));
+    /// assert_eq!(false, predicate_fn.eval("Hello Moon"));
+    /// ```
+    pub fn side_by_side(mut self, width: usize) -> Self {
+        self.options = self.options.side_by_side(width);
+        self
+    }
+
+    /// Show `lines` of unchanged text around each change.
+    ///
+    /// Default: `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello\nWorld").context(3);
+    /// assert_eq!(false, predicate_fn.eval("Hello\nMoon"));
+    /// ```
+    pub fn context(mut self, lines: usize) -> Self {
+        self.options = self.options.context(lines);
+        self
+    }
+
+    /// Name the two sides of the diff.
+    ///
+    /// Default: `orig` and `var`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").labels("expected", "stdout");
+    /// assert_eq!(false, predicate_fn.eval("Hello Moon"));
+    /// ```
+    pub fn labels<E, A>(mut self, expected: E, actual: A) -> Self
+    where
+        E: Into<borrow::Cow<'static, str>>,
+        A: Into<borrow::Cow<'static, str>>,
+    {
+        self.options = self.options.labels(expected, actual);
+        self
+    }
+
+    /// Truncate the diff after `lines`, noting how many lines were left out.
+    ///
+    /// Default: no limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").max_lines(200);
+    /// assert_eq!(false, predicate_fn.eval("Hello Moon"));
+    /// ```
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.options = self.options.max_lines(lines);
+        self
+    }
+
+    /// Make trailing spaces, tabs, and carriage returns visible in the diff.
+    ///
+    /// Trailing spaces are shown as `·`, tabs as `→`, and carriage returns as `␍`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::diff("Hello World").show_whitespace();
+    /// assert_eq!(false, predicate_fn.eval("Hello World  "));
+    /// ```
+    pub fn show_whitespace(mut self) -> Self {
+        self.options = self.options.show_whitespace();
+        self
+    }
 }
 
 impl Predicate<str> for DifferencePredicate {
@@ -30,24 +412,11 @@ impl Predicate<str> for DifferencePredicate {
         if result == expected {
             None
         } else {
-            let palette = crate::Palette::new(true);
-            let orig: Vec<_> = self.orig.lines().map(|l| format!("{l}\n")).collect();
-            let variable: Vec<_> = variable.lines().map(|l| format!("{l}\n")).collect();
-            let diff = difflib::unified_diff(
-                &orig,
-                &variable,
-                "",
-                "",
-                &palette.expected("orig").to_string(),
-                &palette.var("var").to_string(),
-                0,
-            );
-            let mut diff = colorize_diff(diff, palette);
-            diff.insert(0, "\n".to_owned());
-
             Some(
-                reflection::Case::new(Some(self), result)
-                    .add_product(reflection::Product::new("diff", diff.join(""))),
+                reflection::Case::new(Some(self), result).add_product(reflection::Product::new(
+                    "diff",
+                    self.options.render(&self.orig, variable),
+                )),
             )
         }
     }
@@ -90,7 +459,139 @@ pub fn diff<S>(orig: S) -> DifferencePredicate
 where
     S: Into<borrow::Cow<'static, str>>,
 {
-    DifferencePredicate { orig: orig.into() }
+    DifferencePredicate {
+        orig: orig.into(),
+        options: DiffOptions::default(),
+    }
+}
+
+/// Make otherwise invisible whitespace stand out.
+fn show_whitespace(text: &str) -> String {
+    let mut shown = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i != 0 {
+            shown.push('\n');
+        }
+        let (line, cr) = line
+            .strip_suffix('\r')
+            .map(|line| (line, true))
+            .unwrap_or((line, false));
+        let body = line.trim_end_matches(' ');
+        shown.push_str(&body.replace('\t', "→").replace('\r', "␍"));
+        shown.extend(std::iter::repeat_n('·', line.len() - body.len()));
+        if cr {
+            shown.push('␍');
+        }
+    }
+    shown
+}
+
+/// Render one line from each side as rows of a side-by-side diff.
+fn side_by_side_rows(
+    left: Option<&str>,
+    gutter: char,
+    right: Option<&str>,
+    column: usize,
+    palette: crate::Palette,
+) -> Vec<String> {
+    let left = left.map(|line| wrap(line, column)).unwrap_or_default();
+    let right = right.map(|line| wrap(line, column)).unwrap_or_default();
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let left = left.get(i).map(String::as_str).unwrap_or("");
+            let right = right.get(i).map(String::as_str).unwrap_or("");
+            let padding = column.saturating_sub(left.width());
+            let row = if gutter == ' ' {
+                format!("{left}{:padding$} {gutter} {right}", "")
+            } else {
+                format!(
+                    "{:#}{:padding$} {gutter} {:#}",
+                    palette.expected(left),
+                    "",
+                    palette.var(right)
+                )
+            };
+            format!("{}\n", row.trim_end())
+        })
+        .collect()
+}
+
+/// Split a line into chunks of at most `width` display columns, expanding tabs.
+///
+/// Wide characters, like most CJK, take two columns; zero-width characters take none.
+fn wrap(line: &str, width: usize) -> Vec<String> {
+    let mut chunks = vec![String::new()];
+    let mut len = 0;
+    for c in line.chars() {
+        let (c, count, c_width) = if c == '\t' {
+            (' ', TAB_WIDTH - len % TAB_WIDTH, 1)
+        } else {
+            (c, 1, c.width().unwrap_or(0))
+        };
+        for _ in 0..count {
+            if width < len + c_width && 0 < len {
+                chunks.push(String::new());
+                len = 0;
+            }
+            chunks.last_mut().expect("never empty").push(c);
+            len += c_width;
+        }
+    }
+    chunks
+}
+
+/// Format a range of lines the way `difflib::unified_diff` does.
+fn format_range(start: usize, end: usize) -> String {
+    match end - start {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        len => format!("{},{len}", start + 1),
+    }
+}
+
+/// Render a changed pair of lines, marking within each where it differs from the other.
+fn highlight_line(
+    removed: &str,
+    added: &str,
+    mode: DiffMode,
+    palette: crate::Palette,
+) -> (String, String) {
+    let removed_tokens = tokenize(removed, mode);
+    let added_tokens = tokenize(added, mode);
+    if MAX_ALIGNMENT_CELLS < removed_tokens.len().saturating_mul(added_tokens.len()) {
+        return (
+            distance::mark_removed(removed, palette),
+            distance::mark_added(added, palette),
+        );
+    }
+
+    distance::paired_diff(&removed_tokens, &added_tokens, palette)
+}
+
+/// Split a line into the units compared by `mode`.
+///
+/// Words are runs of alphanumeric characters or of whitespace; any other character stands
+/// alone.
+fn tokenize(line: &str, mode: DiffMode) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut last_class = None;
+    for (i, c) in line.char_indices() {
+        let class = match mode {
+            DiffMode::Words if c.is_alphanumeric() || c == '_' => Some(0),
+            DiffMode::Words if c.is_whitespace() => Some(1),
+            _ => None,
+        };
+        if i != 0 && (class.is_none() || class != last_class) {
+            tokens.push(&line[start..i]);
+            start = i;
+        }
+        last_class = class;
+    }
+    if start < line.len() {
+        tokens.push(&line[start..]);
+    }
+    tokens
 }
 
 #[cfg(feature = "color")]
@@ -128,3 +629,92 @@ fn colorize_diff(mut lines: Vec<String>, palette: crate::Palette) -> Vec<String>
 fn colorize_diff(lines: Vec<String>, _palette: crate::Palette) -> Vec<String> {
     lines
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokenize_words() {
+        assert_eq!(
+            tokenize("foo v1.2  bar", DiffMode::Words),
+            ["foo", " ", "v1", ".", "2", "  ", "bar"]
+        );
+    }
+
+    #[test]
+    fn tokenize_chars() {
+        assert_eq!(tokenize("añb", DiffMode::Chars), ["a", "ñ", "b"]);
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(show_whitespace("a\tb  \r\nc "), "a→b··␍\nc·");
+    }
+
+    #[test]
+    fn wrap_lines() {
+        assert_eq!(wrap("", 4), [""]);
+        assert_eq!(wrap("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("a\tb", 8), ["a   b"]);
+        assert_eq!(wrap("日本語日本語", 5), ["日本", "語日", "本語"]);
+    }
+
+    #[test]
+    fn side_by_side() {
+        let palette = crate::Palette::plain();
+        assert_eq!(
+            side_by_side_rows(Some("abcdefghijkl"), '|', Some("abc"), 10, palette),
+            ["abcdefghij | abc\n", "kl         |\n"]
+        );
+        assert_eq!(
+            side_by_side_rows(None, '>', Some("abc"), 10, palette),
+            ["           > abc\n"]
+        );
+        assert_eq!(
+            side_by_side_rows(Some("日本語日本語日本語日"), '|', Some("abc"), 10, palette),
+            ["日本語日本 | abc\n", "語日本語日 |\n"]
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let options = DiffOptions {
+            max_lines: Some(3),
+            ..Default::default()
+        };
+        let diff = options.render("a\nb\nc", "x\ny\nz");
+        assert_eq!(diff.lines().count(), 5);
+        assert!(diff.contains("... 6 more lines truncated"));
+    }
+
+    #[test]
+    fn highlight_words() {
+        let palette = crate::Palette::plain();
+        assert_eq!(
+            highlight_line("let x = 1;", "let y = 1;", DiffMode::Words, palette),
+            ("let [-x-] = 1;".to_owned(), "let {+y+} = 1;".to_owned())
+        );
+        assert_eq!(
+            highlight_line("Hello World", "Hello Word", DiffMode::Chars, palette),
+            ("Hello Wor[-l-]d".to_owned(), "Hello Word".to_owned())
+        );
+    }
+
+    #[test]
+    fn inline_keeps_line_prefixes() {
+        let options = DiffOptions::default().by_words().context(1);
+        let palette = crate::Palette::plain();
+        assert_eq!(
+            options.render_inline("a\nb c\nd\n", "a\nb x\ne\nf\n", palette)[3..],
+            [
+                " a\n",
+                "-b [-c-]\n",
+                "-[-d-]\n",
+                "+b {+x+}\n",
+                "+{+e+}\n",
+                "+{+f+}\n"
+            ]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow;
+use std::fmt;
+use std::str;
+
+use crate::Predicate;
+use crate::reflection;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    /// UTF-8 or UTF-16, as identified by a byte order mark, defaulting to UTF-8.
+    Text,
+}
+
+/// Where and why decoding failed, for an `error` product.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DecodeError {
+    pub(crate) offset: usize,
+    pub(crate) reason: String,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} from index {}", self.reason, self.offset)
+    }
+}
+
+impl Encoding {
+    fn decode<'s>(self, variable: &'s [u8]) -> Result<borrow::Cow<'s, str>, DecodeError> {
+        match self {
+            Encoding::Utf16Le => {
+                decode_utf16(strip(variable, UTF16LE_BOM), variable, u16::from_le_bytes)
+            }
+            Encoding::Utf16Be => {
+                decode_utf16(strip(variable, UTF16BE_BOM), variable, u16::from_be_bytes)
+            }
+            Encoding::Latin1 => Ok(borrow::Cow::Owned(
+                variable.iter().map(|b| char::from(*b)).collect(),
+            )),
+            Encoding::Text => {
+                if variable.starts_with(UTF16LE_BOM) {
+                    Encoding::Utf16Le.decode(variable)
+                } else if variable.starts_with(UTF16BE_BOM) {
+                    Encoding::Utf16Be.decode(variable)
+                } else {
+                    let text = strip(variable, UTF8_BOM);
+                    let bom = variable.len() - text.len();
+                    str::from_utf8(text)
+                        .map(borrow::Cow::Borrowed)
+                        .map_err(|err| DecodeError {
+                            offset: bom + err.valid_up_to(),
+                            reason: match err.error_len() {
+                                Some(len) => format!("invalid utf-8 sequence of {len} bytes"),
+                                None => "incomplete utf-8 byte sequence".to_owned(),
+                            },
+                        })
+                }
+            }
+        }
+    }
+}
+
+fn strip<'s>(variable: &'s [u8], bom: &[u8]) -> &'s [u8] {
+    variable.strip_prefix(bom).unwrap_or(variable)
+}
+
+/// Decode the UTF-16 `text`, reporting offsets relative to the start of `variable`.
+fn decode_utf16<'s>(
+    text: &[u8],
+    variable: &[u8],
+    from_bytes: fn([u8; 2]) -> u16,
+) -> Result<borrow::Cow<'s, str>, DecodeError> {
+    let bom = variable.len() - text.len();
+    let units = text.chunks_exact(2);
+    if !units.remainder().is_empty() {
+        return Err(DecodeError {
+            offset: variable.len() - 1,
+            reason: "incomplete utf-16 code unit".to_owned(),
+        });
+    }
+    let units = units.map(|pair| from_bytes([pair[0], pair[1]]));
+    let mut decoded = String::with_capacity(text.len() / 2);
+    let mut offset = bom;
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok(c) => {
+                decoded.push(c);
+                offset += 2 * c.len_utf16();
+            }
+            Err(err) => {
+                return Err(DecodeError {
+                    offset,
+                    reason: format!(
+                        "unpaired utf-16 surrogate 0x{:04x}",
+                        err.unpaired_surrogate()
+                    ),
+                });
+            }
+        }
+    }
+    Ok(borrow::Cow::Owned(decoded))
+}
+
+/// Predicate adapter that decodes bytes in a text encoding other than UTF-8 for a `str`
+/// predicate.
+///
+/// This is created by `pred.from_utf16le()`, `pred.from_utf16be()`, `pred.from_latin1()`, and
+/// `pred.from_text()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodePredicate<P>
+where
+    P: Predicate<str>,
+{
+    pub(crate) p: P,
+    pub(crate) encoding: Encoding,
+}
+
+impl<P> Predicate<[u8]> for DecodePredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn eval(&self, variable: &[u8]) -> bool {
+        self.encoding
+            .decode(variable)
+            .map(|s| self.p.eval(&s))
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &[u8]) -> Option<reflection::Case<'a>> {
+        let var_str = self.encoding.decode(variable);
+        match (expected, var_str) {
+            (_, Ok(var_str)) => self.p.find_case(expected, &var_str).map(|child| {
+                child.add_product(reflection::Product::new("var as str", var_str.into_owned()))
+            }),
+            (true, Err(_)) => None,
+            (false, Err(err)) => Some(
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("error", err)),
+            ),
+        }
+    }
+}
+
+impl<P> reflection::PredicateReflection for DecodePredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for DecodePredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.p.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn utf16_with_bom() {
+        let decoded = Encoding::Text.decode(b"\xFF\xFEH\x00i\x00=\xD8\x00\xDE");
+        assert_eq!(decoded.unwrap(), "Hi\u{1F600}");
+        let decoded = Encoding::Utf16Be.decode(b"\x00H\x00i");
+        assert_eq!(decoded.unwrap(), "Hi");
+    }
+
+    #[test]
+    fn utf16_errors() {
+        let err = Encoding::Utf16Le
+            .decode(b"\xFF\xFEH\x00\x00\xD8i\x00")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unpaired utf-16 surrogate 0xd800 from index 4"
+        );
+        let err = Encoding::Utf16Le.decode(b"H\x00i").unwrap_err();
+        assert_eq!(err.to_string(), "incomplete utf-16 code unit from index 2");
+    }
+
+    #[test]
+    fn utf8_errors_include_bom() {
+        let err = Encoding::Text.decode(b"\xEF\xBB\xBFab\xFF").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid utf-8 sequence of 1 bytes from index 5"
+        );
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::error::Error;
+use std::fmt;
+use std::ops;
+
+use crate::Predicate;
+use crate::reflection;
+use crate::utils;
+
+/// An error that occurred while parsing a glob pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobError {
+    pattern: String,
+    offset: usize,
+    reason: &'static str,
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid glob {:?} at offset {}: {}",
+            self.pattern, self.offset, self.reason
+        )
+    }
+}
+
+impl Error for GlobError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    AnySequence,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Literal(l) => *l == c,
+            Token::AnyChar | Token::AnySequence => true,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Glob {
+    source: String,
+    tokens: Vec<(Token, ops::Range<usize>)>,
+}
+
+impl Glob {
+    fn parse(source: &str) -> Result<Self, GlobError> {
+        let error = |offset, reason| GlobError {
+            pattern: source.to_owned(),
+            offset,
+            reason,
+        };
+
+        let mut tokens: Vec<(Token, ops::Range<usize>)> = Vec::new();
+        let mut chars = source.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            let token = match c {
+                '*' => {
+                    if let Some((Token::AnySequence, span)) = tokens.last_mut() {
+                        span.end = start + 1;
+                        continue;
+                    }
+                    Token::AnySequence
+                }
+                '?' => Token::AnyChar,
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => Token::Literal(escaped),
+                    None => return Err(error(start, "trailing escape")),
+                },
+                '[' => {
+                    let negated = chars.next_if(|&(_, c)| c == '!' || c == '^').is_some();
+                    let mut ranges = Vec::new();
+                    let mut closed = false;
+                    let mut first = true;
+                    while let Some((offset, c)) = chars.next() {
+                        let lo = match c {
+                            ']' if !first => {
+                                closed = true;
+                                break;
+                            }
+                            '\\' => match chars.next() {
+                                Some((_, escaped)) => escaped,
+                                None => return Err(error(offset, "trailing escape")),
+                            },
+                            c => c,
+                        };
+                        first = false;
+                        let mut lookahead = chars.clone();
+                        let is_range = lookahead.next().map(|(_, c)| c == '-').unwrap_or(false)
+                            && lookahead.next().map(|(_, c)| c != ']').unwrap_or(false);
+                        let hi = if is_range {
+                            chars.next();
+                            match chars.next() {
+                                Some((_, '\\')) => match chars.next() {
+                                    Some((_, escaped)) => escaped,
+                                    None => return Err(error(offset, "trailing escape")),
+                                },
+                                Some((_, hi)) => hi,
+                                None => return Err(error(start, "unterminated character class")),
+                            }
+                        } else {
+                            lo
+                        };
+                        if hi < lo {
+                            return Err(error(offset, "character range is out of order"));
+                        }
+                        ranges.push((lo, hi));
+                    }
+                    if !closed {
+                        return Err(error(start, "unterminated character class"));
+                    }
+                    Token::Class { negated, ranges }
+                }
+                c => Token::Literal(c),
+            };
+            let end = chars.peek().map(|&(i, _)| i).unwrap_or(source.len());
+            tokens.push((token, start..end));
+        }
+
+        Ok(Self {
+            source: source.to_owned(),
+            tokens,
+        })
+    }
+
+    /// Simulate the pattern over `text`, returning whether it matched and how far the most
+    /// successful attempt got.
+    ///
+    /// States are token indices, with `tokens.len()` being the accepting state.  Each live state
+    /// remembers the earliest offset its attempt started from.
+    fn run(&self, text: &str, anchored: bool) -> Progress {
+        let accept = self.tokens.len();
+        let mut states: Vec<Option<usize>> = vec![None; accept + 1];
+        let mut progress = Progress::default();
+
+        self.seed(&mut states, 0);
+        progress.record(&states, 0);
+        for (offset, c) in text.char_indices() {
+            if states[accept].is_some() && !anchored {
+                break;
+            }
+            let mut next = vec![None; accept + 1];
+            for (state, start) in states.iter().enumerate() {
+                let Some(start) = *start else {
+                    continue;
+                };
+                let Some((token, _)) = self.tokens.get(state) else {
+                    continue;
+                };
+                if token.matches(c) {
+                    let target = if *token == Token::AnySequence {
+                        state
+                    } else {
+                        state + 1
+                    };
+                    merge(&mut next, target, start);
+                }
+            }
+            let end = offset + c.len_utf8();
+            self.close(&mut next);
+            if !anchored {
+                self.seed(&mut next, end);
+            }
+            states = next;
+            progress.record(&states, end);
+        }
+
+        progress.matched = states[accept].is_some() || (!anchored && progress.state == accept);
+        progress
+    }
+
+    fn seed(&self, states: &mut [Option<usize>], start: usize) {
+        merge(states, 0, start);
+        self.close(states);
+    }
+
+    /// Follow the empty transitions allowed by `*`.
+    fn close(&self, states: &mut [Option<usize>]) {
+        for state in 0..self.tokens.len() {
+            if let (Some(start), (Token::AnySequence, _)) = (states[state], &self.tokens[state]) {
+                merge(states, state + 1, start);
+            }
+        }
+    }
+
+    /// The portion of the pattern consumed to reach `state`.
+    fn prefix(&self, state: usize) -> &str {
+        let end = state
+            .checked_sub(1)
+            .map(|i| self.tokens[i].1.end)
+            .unwrap_or(0);
+        &self.source[..end]
+    }
+}
+
+fn merge(states: &mut [Option<usize>], state: usize, start: usize) {
+    let slot = &mut states[state];
+    *slot = Some(slot.map(|s| s.min(start)).unwrap_or(start));
+}
+
+#[derive(Debug, Default)]
+struct Progress {
+    matched: bool,
+    state: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Progress {
+    fn record(&mut self, states: &[Option<usize>], end: usize) {
+        if let Some((state, start)) = states
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(state, start)| start.map(|start| (state, start)))
+        {
+            if (state, end) >= (self.state, self.end) {
+                self.state = state;
+                self.start = start;
+                self.end = end;
+            }
+        }
+    }
+}
+
+/// Predicate that matches a string against a glob pattern.
+///
+/// This is created by the `predicate::str::glob`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobPredicate {
+    glob: Glob,
+    contains: bool,
+}
+
+impl GlobPredicate {
+    /// Match the pattern anywhere within the string.
+    ///
+    /// When yes is false, the pattern must match the entire string.
+    ///
+    /// Default: disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::glob("v?.*.*").unwrap().contains(true);
+    /// assert_eq!(true, predicate_fn.eval("Compiling foo v1.2.3"));
+    /// assert_eq!(false, predicate_fn.eval("Compiling foo"));
+    /// ```
+    pub fn contains(mut self, yes: bool) -> Self {
+        self.contains = yes;
+        self
+    }
+}
+
+impl Predicate<str> for GlobPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.glob.run(variable, !self.contains).matched
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        let progress = self.glob.run(variable, !self.contains);
+        if progress.matched != expected {
+            return None;
+        }
+
+        let mut case = reflection::Case::new(Some(self), progress.matched)
+            .add_product(reflection::Product::new("var", variable.to_owned()));
+        if !progress.matched {
+            case = case
+                .add_product(reflection::Product::new(
+                    "matched pattern",
+                    utils::DebugAdapter::new(self.glob.prefix(progress.state).to_owned()),
+                ))
+                .add_product(reflection::Product::new(
+                    "matched text",
+                    utils::DebugAdapter::new(variable[progress.start..progress.end].to_owned()),
+                ))
+                .add_product(reflection::Product::new("diverged at", progress.end));
+        }
+        Some(case)
+    }
+}
+
+impl reflection::PredicateReflection for GlobPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params = vec![reflection::Parameter::new("contains", &self.contains)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for GlobPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("glob"),
+            palette.expected(utils::DebugAdapter::new(&self.glob.source)),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that matches the string against a glob pattern.
+///
+/// The pattern supports:
+/// - `*`: any sequence of characters, including none
+/// - `?`: any single character
+/// - `[abc]`, `[a-z]`: any character in the class; `[!abc]` or `[^abc]` negates it
+/// - `\`: escapes the next character
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::glob("Compiling * v*.*.*").unwrap();
+/// assert_eq!(true, predicate_fn.eval("Compiling foo v1.2.3"));
+/// assert_eq!(false, predicate_fn.eval("Compiling foo"));
+///
+/// let predicate_fn = predicate::str::glob(r"[a-z]?\*").unwrap();
+/// assert_eq!(true, predicate_fn.eval("ab*"));
+/// assert_eq!(false, predicate_fn.eval("abc"));
+/// ```
+pub fn glob<S>(pattern: S) -> Result<GlobPredicate, GlobError>
+where
+    S: AsRef<str>,
+{
+    Glob::parse(pattern.as_ref()).map(|glob| GlobPredicate {
+        glob,
+        contains: false,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn literal_and_wildcards() {
+        let p = predicate::str::glob("a*b?c").unwrap();
+        assert!(p.eval("abxc"));
+        assert!(p.eval("a123bxc"));
+        assert!(!p.eval("abc"));
+        assert!(!p.eval("abxcd"));
+    }
+
+    #[test]
+    fn classes() {
+        let p = predicate::str::glob("[a-c][!0-9][]x]").unwrap();
+        assert!(p.eval("ab]"));
+        assert!(p.eval("cxx"));
+        assert!(!p.eval("a1x"));
+        assert!(!p.eval("dxx"));
+
+        let p = predicate::str::glob("[a-]").unwrap();
+        assert!(p.eval("-"));
+        assert!(p.eval("a"));
+    }
+
+    #[test]
+    fn escapes() {
+        let p = predicate::str::glob(r"\*\?\[").unwrap();
+        assert!(p.eval("*?["));
+        assert!(!p.eval("a?["));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(predicate::str::glob("[abc").is_err());
+        assert!(predicate::str::glob("abc\\").is_err());
+        assert!(predicate::str::glob("[z-a]").is_err());
+    }
+
+    #[test]
+    fn contains() {
+        let p = predicate::str::glob("b?d").unwrap().contains(true);
+        assert!(p.eval("abcde"));
+        assert!(!p.eval("abcce"));
+        assert!(predicate::str::glob("").unwrap().contains(true).eval("x"));
+    }
+
+    #[test]
+    fn find_case_reports_divergence() {
+        let p = predicate::str::glob("foo*bar").unwrap();
+        let case = p.find_case(false, "foo baz").unwrap();
+        let products: Vec<_> = case.products().map(|p| p.to_string()).collect();
+        assert_eq!(
+            products,
+            [
+                "var: foo baz",
+                "matched pattern: \"foo*ba\"",
+                "matched text: \"foo ba\"",
+                "diverged at: 6",
+            ]
+        );
+        assert!(p.find_case(true, "foo baz").is_none());
+    }
+
+    #[test]
+    fn find_case_contains() {
+        let p = predicate::str::glob("v1.?.3").unwrap().contains(true);
+        let case = p.find_case(false, "Compiling foo v1.2.4").unwrap();
+        let products: Vec<_> = case.products().map(|p| p.to_string()).collect();
+        assert_eq!(products[1], "matched pattern: \"v1.?.\"");
+        assert_eq!(products[2], "matched text: \"v1.2.\"");
+    }
+}
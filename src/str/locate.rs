@@ -0,0 +1,240 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Describe where patterns matched within a string.
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::distance;
+use crate::reflection;
+
+/// Matches listed before the rest are summarized.
+const MAX_MATCHES: usize = 10;
+/// Characters of the surrounding line shown on either side of a match.
+const EXCERPT_CHARS: usize = 20;
+/// Upper bound on the table size used to search for a near miss.
+const MAX_SEARCH_CELLS: usize = 1 << 22;
+
+/// Where a pattern is required to appear within the variable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Anchor {
+    Anywhere,
+    Start,
+    End,
+}
+
+/// Render `variable` with each of the `spans` highlighted.
+///
+/// The highlight follows the formatter's alternate flag, like the predicates' own `Display`.
+pub(crate) fn highlight(variable: &str, spans: &[Range<usize>]) -> Highlight {
+    Highlight {
+        variable: variable.to_owned(),
+        spans: spans.to_vec(),
+    }
+}
+
+pub(crate) struct Highlight {
+    variable: String,
+    spans: Vec<Range<usize>>,
+}
+
+impl fmt::Display for Highlight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        let mut last = 0;
+        for span in &self.spans {
+            write!(
+                f,
+                "{}{:#}",
+                &self.variable[last..span.start],
+                palette.expected(&self.variable[span.clone()])
+            )?;
+            last = span.end;
+        }
+        write!(f, "{}", &self.variable[last..])
+    }
+}
+
+/// Describe each of the `spans` with its line, column, and an excerpt of the surrounding line.
+pub(crate) fn locations(variable: &str, spans: &[Range<usize>]) -> Vec<reflection::Product> {
+    let mut products: Vec<_> = spans
+        .iter()
+        .take(MAX_MATCHES)
+        .enumerate()
+        .map(|(i, span)| {
+            reflection::Product::new(format!("match {}", i + 1), locate(variable, span))
+        })
+        .collect();
+    if MAX_MATCHES < spans.len() {
+        products.push(reflection::Product::new(
+            "more matches",
+            spans.len() - MAX_MATCHES,
+        ));
+    }
+    products
+}
+
+/// Describe the run of `variable` closest to `pattern` by edit distance, with its location and
+/// how it differs from `pattern`.
+///
+/// Nothing is reported when every run is further from `pattern` than `pattern` is long.
+pub(crate) fn closest_match(
+    variable: &str,
+    pattern: &str,
+    anchor: Anchor,
+) -> Option<reflection::Product> {
+    let mut text: Vec<_> = variable.char_indices().collect();
+    let mut needle: Vec<_> = pattern.chars().collect();
+    if needle.is_empty() || MAX_SEARCH_CELLS < text.len().saturating_mul(needle.len()) {
+        return None;
+    }
+    if anchor == Anchor::End {
+        text.reverse();
+        needle.reverse();
+    }
+    let chars: Vec<_> = text.iter().map(|(_, c)| *c).collect();
+    let (range, distance) =
+        distance::closest_substring(&needle, &chars, anchor != Anchor::Anywhere);
+    if needle.len() <= distance || range.is_empty() {
+        return None;
+    }
+
+    let (first, last) = (text[range.start].0, text[range.end - 1].0);
+    let span =
+        first.min(last)..first.max(last) + variable[first.max(last)..].chars().next()?.len_utf8();
+    let (line, column) = position(variable, span.start);
+    Some(reflection::Product::new(
+        "closest match",
+        ClosestMatch {
+            line,
+            column,
+            snippet: variable[span].to_owned(),
+            pattern: pattern.to_owned(),
+            distance,
+        },
+    ))
+}
+
+struct ClosestMatch {
+    line: usize,
+    column: usize,
+    snippet: String,
+    pattern: String,
+    distance: usize,
+}
+
+impl fmt::Display for ClosestMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let diff = distance::inline_diff(
+            &self.pattern.split_inclusive(|_| true).collect::<Vec<_>>(),
+            &self.snippet.split_inclusive(|_| true).collect::<Vec<_>>(),
+            crate::Palette::new(f.alternate()),
+        );
+        write!(
+            f,
+            "{}:{}: {:?} (distance {}): {diff}",
+            self.line, self.column, self.snippet, self.distance
+        )
+    }
+}
+
+/// The 1-based line and column of the byte `offset` within `variable`.
+fn position(variable: &str, offset: usize) -> (usize, usize) {
+    let before = &variable[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+fn locate(variable: &str, span: &Range<usize>) -> String {
+    let before = &variable[..span.start];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let (line, column) = position(variable, span.start);
+
+    let after = &variable[span.end..];
+    let after = &after[..after.find('\n').unwrap_or(after.len())];
+    let prefix = &before[line_start..];
+    let prefix_chars = prefix.chars().count();
+    let prefix = if EXCERPT_CHARS < prefix_chars {
+        let skip = prefix
+            .char_indices()
+            .nth(prefix_chars - EXCERPT_CHARS)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        format!("...{}", &prefix[skip..])
+    } else {
+        prefix.to_owned()
+    };
+    let suffix = match after.char_indices().nth(EXCERPT_CHARS) {
+        Some((i, _)) => format!("{}...", &after[..i]),
+        None => after.to_owned(),
+    };
+    let excerpt = format!("{prefix}{}{suffix}", &variable[span.clone()]);
+    format!("{line}:{column}: {excerpt:?}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locate_line_and_column() {
+        let variable = "first line\nsecond ✓ line\n";
+        let start = variable.find("line\n").unwrap();
+        assert_eq!(locate(variable, &(start..start + 4)), "1:7: \"first line\"");
+        let start = variable.rfind("line").unwrap();
+        assert_eq!(
+            locate(variable, &(start..start + 4)),
+            "2:10: \"second ✓ line\""
+        );
+    }
+
+    #[test]
+    fn highlight_follows_alternate() {
+        let highlighted = highlight("a foo b foo", &[2..5, 8..11]);
+        assert_eq!(highlighted.to_string(), "a foo b foo");
+        #[cfg(feature = "color")]
+        assert_ne!(format!("{highlighted:#}"), "a foo b foo");
+    }
+
+    #[test]
+    fn closest_match_reports_near_miss() {
+        let product = closest_match(
+            "warning: unused\n   compiling  foo v1.0\n",
+            "Compiling foo",
+            Anchor::Anywhere,
+        )
+        .unwrap();
+        assert_eq!(
+            product.value().to_string(),
+            "2:4: \"compiling  foo\" (distance 2): [-C-]{+c+}ompiling{+ +} foo"
+        );
+
+        let product = closest_match("xyz foo", "foo", Anchor::End).unwrap();
+        assert_eq!(
+            product.value().to_string(),
+            "1:5: \"foo\" (distance 0): foo"
+        );
+        assert!(closest_match("abc", "xyz", Anchor::Start).is_none());
+    }
+
+    #[test]
+    fn locate_trims_long_lines() {
+        let variable = format!("{}needle{}", "a".repeat(30), "b".repeat(30));
+        assert_eq!(
+            locate(&variable, &(30..36)),
+            format!(
+                "1:31: \"...{}needle{}...\"",
+                "a".repeat(EXCERPT_CHARS),
+                "b".repeat(EXCERPT_CHARS)
+            )
+        );
+    }
+}
@@ -14,11 +14,27 @@ mod basics;
 pub use self::basics::*;
 mod adapters;
 pub use self::adapters::*;
+pub(crate) mod encoding;
+pub use self::encoding::DecodePredicate;
+mod redact;
+pub use self::redact::{RedactPredicate, Redaction};
+mod glob;
+mod locate;
+mod similar;
+pub use self::glob::{GlobError, GlobPredicate, glob};
+pub use self::similar::{SimilarPredicate, similar_to};
+pub use crate::path::snapshot::matches_file;
 
 #[cfg(feature = "diff")]
 mod difference;
 #[cfg(feature = "diff")]
+pub(crate) use self::difference::DiffOptions;
+#[cfg(feature = "diff")]
 pub use self::difference::{DifferencePredicate, diff};
+#[cfg(feature = "diff")]
+mod snapshot;
+#[cfg(feature = "diff")]
+pub use self::snapshot::{SnapshotPredicate, matches_snapshot};
 #[cfg(feature = "normalize-line-endings")]
 mod normalize;
 #[cfg(feature = "normalize-line-endings")]
@@ -27,4 +43,7 @@ pub use self::normalize::NormalizedPredicate;
 #[cfg(feature = "regex")]
 mod regex;
 #[cfg(feature = "regex")]
-pub use self::regex::{RegexError, RegexMatchesPredicate, RegexPredicate, is_match};
+pub use self::regex::{
+    RegexCapturePredicate, RegexError, RegexMatchesPredicate, RegexPredicate, RegexSetPredicate,
+    is_match, matches_all, matches_any,
+};
@@ -0,0 +1,242 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow;
+use std::fmt;
+
+use crate::Predicate;
+use crate::reflection;
+
+#[derive(Debug, Clone)]
+enum Pattern {
+    Literal(borrow::Cow<'static, str>),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+/// A substitution applied to the variable by `pred.redact()`.
+#[derive(Debug, Clone)]
+pub struct Redaction {
+    pattern: Pattern,
+    replacement: borrow::Cow<'static, str>,
+}
+
+impl Redaction {
+    /// Replace every occurrence of `pattern` with `replacement`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::str::Redaction;
+    ///
+    /// let redaction = Redaction::literal("/tmp/abc123", "[ROOT]");
+    /// ```
+    pub fn literal<S, R>(pattern: S, replacement: R) -> Self
+    where
+        S: Into<borrow::Cow<'static, str>>,
+        R: Into<borrow::Cow<'static, str>>,
+    {
+        Self {
+            pattern: Pattern::Literal(pattern.into()),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Replace every match of the regular expression `pattern` with `replacement`.
+    ///
+    /// Capture groups may be referenced from `replacement`, like `$1` or `${name}`.  Use `$$` for
+    /// a literal `$`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::str::Redaction;
+    ///
+    /// let redaction = Redaction::regex(r"\d+ms", "[DURATION]").unwrap();
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn regex<S, R>(pattern: S, replacement: R) -> Result<Self, crate::str::RegexError>
+    where
+        S: AsRef<str>,
+        R: Into<borrow::Cow<'static, str>>,
+    {
+        Ok(Self {
+            pattern: Pattern::Regex(regex::Regex::new(pattern.as_ref())?),
+            replacement: replacement.into(),
+        })
+    }
+
+    fn apply<'s>(&self, variable: borrow::Cow<'s, str>) -> borrow::Cow<'s, str> {
+        match &self.pattern {
+            Pattern::Literal(pattern) => {
+                if !pattern.is_empty() && variable.contains(pattern.as_ref()) {
+                    borrow::Cow::Owned(variable.replace(pattern.as_ref(), &self.replacement))
+                } else {
+                    variable
+                }
+            }
+            #[cfg(feature = "regex")]
+            Pattern::Regex(re) => match re.replace_all(&variable, self.replacement.as_ref()) {
+                borrow::Cow::Borrowed(_) => variable,
+                borrow::Cow::Owned(replaced) => borrow::Cow::Owned(replaced),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Redaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Pattern::Literal(pattern) => write!(f, "{pattern:?}")?,
+            #[cfg(feature = "regex")]
+            Pattern::Regex(re) => write!(f, "/{re}/")?,
+        }
+        write!(f, " -> {:?}", self.replacement)
+    }
+}
+
+/// Predicate adapter that rewrites volatile parts of the variable being tested.
+///
+/// This is created by `pred.redact()`.
+#[derive(Debug, Clone)]
+pub struct RedactPredicate<P>
+where
+    P: Predicate<str>,
+{
+    pub(crate) p: P,
+    pub(crate) redactions: Vec<Redaction>,
+}
+
+impl<P> RedactPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn redact<'s>(&self, variable: &'s str) -> borrow::Cow<'s, str> {
+        self.redactions
+            .iter()
+            .fold(borrow::Cow::Borrowed(variable), |variable, redaction| {
+                redaction.apply(variable)
+            })
+    }
+}
+
+impl<P> Predicate<str> for RedactPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn eval(&self, variable: &str) -> bool {
+        self.p.eval(&self.redact(variable))
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        self.p
+            .find_case(expected, &self.redact(variable))
+            .map(|child| reflection::Case::new(Some(self), child.result()).add_child(child))
+    }
+}
+
+impl<P> reflection::PredicateReflection for RedactPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params: Vec<_> = self
+            .redactions
+            .iter()
+            .map(|redaction| reflection::Parameter::new("redaction", redaction))
+            .collect();
+        Box::new(params.into_iter())
+    }
+
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params = vec![reflection::Child::new("predicate", &self.p)];
+        Box::new(params.into_iter())
+    }
+}
+
+impl<P> fmt::Display for RedactPredicate<P>
+where
+    P: Predicate<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}()",
+            palette.var("var"),
+            palette.description("redact"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn redact(variable: &str, redactions: Vec<Redaction>) -> String {
+        RedactPredicate {
+            p: crate::constant::always(),
+            redactions,
+        }
+        .redact(variable)
+        .into_owned()
+    }
+
+    #[test]
+    fn redactions_apply_in_order() {
+        let redactions = vec![
+            Redaction::literal("/tmp/abc", "[ROOT]"),
+            Redaction::literal("[ROOT]/out", "[OUT]"),
+        ];
+        assert_eq!(redact("/tmp/abc/out.txt", redactions), "[OUT].txt");
+
+        let redactions = vec![
+            Redaction::literal("[ROOT]/out", "[OUT]"),
+            Redaction::literal("/tmp/abc", "[ROOT]"),
+        ];
+        assert_eq!(redact("/tmp/abc/out.txt", redactions), "[ROOT]/out.txt");
+    }
+
+    #[test]
+    fn empty_literal_is_ignored() {
+        let redactions = vec![Redaction::literal("", "[EMPTY]")];
+        assert_eq!(redact("abc", redactions), "abc");
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn regex_replacement_uses_captures() {
+        let redactions = vec![Redaction::regex(r"(\d+)ms", "[$1 MS]").unwrap()];
+        assert_eq!(redact("took 12ms", redactions), "took [12 MS]");
+    }
+
+    #[test]
+    fn redactions_are_parameters() {
+        use reflection::PredicateReflection as _;
+
+        let p = RedactPredicate {
+            p: crate::constant::always(),
+            redactions: vec![
+                Redaction::literal("/tmp/abc", "[ROOT]"),
+                Redaction::literal("12ms", "[DURATION]"),
+            ],
+        };
+        let params: Vec<_> = p
+            .parameters()
+            .map(|param| format!("{}: {}", param.name(), param.value()))
+            .collect();
+        assert_eq!(
+            params,
+            [
+                "redaction: \"/tmp/abc\" -> \"[ROOT]\"",
+                "redaction: \"12ms\" -> \"[DURATION]\"",
+            ]
+        );
+        assert_eq!(p.children().count(), 1);
+    }
+}
@@ -7,9 +7,11 @@
 // except according to those terms.
 
 use std::fmt;
+use std::sync;
 
 use crate::Predicate;
 use crate::reflection;
+use crate::str::locate;
 use crate::utils;
 
 /// An error that occurred during parsing or compiling a regular expression.
@@ -38,6 +40,67 @@ impl RegexPredicate {
     pub fn count(self, count: usize) -> RegexMatchesPredicate {
         RegexMatchesPredicate { re: self.re, count }
     }
+
+    /// Require the named capture group of the first match to satisfy `p`.
+    ///
+    /// # Panics
+    ///
+    /// If the regex has no group named `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::is_match(r"port (?<port>\d+)")
+    ///     .unwrap()
+    ///     .capture("port", predicate::str::starts_with("80"));
+    /// assert_eq!(true, predicate_fn.eval("listening on port 8080"));
+    /// assert_eq!(false, predicate_fn.eval("listening on port 443"));
+    /// assert_eq!(false, predicate_fn.eval("not listening"));
+    /// ```
+    pub fn capture<S, P>(self, name: S, p: P) -> RegexCapturePredicate
+    where
+        S: Into<String>,
+        P: Predicate<str> + Send + Sync + 'static,
+    {
+        RegexCapturePredicate {
+            re: self.re,
+            captures: Vec::new(),
+        }
+        .capture(name, p)
+    }
+
+    /// Require the numbered capture group of the first match to satisfy `p`.
+    ///
+    /// Group `0` is the whole match.
+    ///
+    /// # Panics
+    ///
+    /// If the regex has no group numbered `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::is_match(r"v(\d+)\.(\d+)")
+    ///     .unwrap()
+    ///     .capture_index(1, predicate::eq("1"))
+    ///     .capture_index(2, predicate::str::is_match(r"^[2-9]$").unwrap());
+    /// assert_eq!(true, predicate_fn.eval("version v1.4"));
+    /// assert_eq!(false, predicate_fn.eval("version v1.1"));
+    /// ```
+    pub fn capture_index<P>(self, index: usize, p: P) -> RegexCapturePredicate
+    where
+        P: Predicate<str> + Send + Sync + 'static,
+    {
+        RegexCapturePredicate {
+            re: self.re,
+            captures: Vec::new(),
+        }
+        .capture_index(index, p)
+    }
 }
 
 impl Predicate<str> for RegexPredicate {
@@ -46,8 +109,16 @@ impl Predicate<str> for RegexPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        utils::default_find_case(self, expected, variable)
-            .map(|case| case.add_product(reflection::Product::new("var", variable.to_owned())))
+        utils::default_find_case(self, expected, variable).map(|case| {
+            let spans: Vec<_> = self.re.find_iter(variable).map(|m| m.range()).collect();
+            let case = case.add_product(reflection::Product::new(
+                "var",
+                locate::highlight(variable, &spans),
+            ));
+            locate::locations(variable, &spans)
+                .into_iter()
+                .fold(case, |case, product| case.add_product(product))
+        })
     }
 }
 
@@ -81,13 +152,20 @@ impl Predicate<str> for RegexMatchesPredicate {
     }
 
     fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
-        let actual_count = self.re.find_iter(variable).count();
+        let spans: Vec<_> = self.re.find_iter(variable).map(|m| m.range()).collect();
+        let actual_count = spans.len();
         let result = self.count == actual_count;
         if result == expected {
+            let case = reflection::Case::new(Some(self), result)
+                .add_product(reflection::Product::new(
+                    "var",
+                    locate::highlight(variable, &spans),
+                ))
+                .add_product(reflection::Product::new("actual count", actual_count));
             Some(
-                reflection::Case::new(Some(self), result)
-                    .add_product(reflection::Product::new("var", variable.to_owned()))
-                    .add_product(reflection::Product::new("actual count", actual_count)),
+                locate::locations(variable, &spans)
+                    .into_iter()
+                    .fold(case, |case, product| case.add_product(product)),
             )
         } else {
             None
@@ -115,6 +193,162 @@ impl fmt::Display for RegexMatchesPredicate {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CaptureGroup {
+    Name(String),
+    Index(usize),
+}
+
+impl fmt::Display for CaptureGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureGroup::Name(name) => name.fmt(f),
+            CaptureGroup::Index(index) => index.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Capture {
+    group: CaptureGroup,
+    label: String,
+    p: sync::Arc<crate::BoxPredicate<str>>,
+}
+
+/// Predicate that checks the capture groups of the first regex match.
+///
+/// This is created by `predicates::str::is_match(...).capture` and
+/// `predicates::str::is_match(...).capture_index`.
+#[derive(Debug, Clone)]
+pub struct RegexCapturePredicate {
+    re: regex::Regex,
+    captures: Vec<Capture>,
+}
+
+impl RegexCapturePredicate {
+    /// Also require the named capture group of the first match to satisfy `p`.
+    ///
+    /// See [`RegexPredicate::capture`].
+    pub fn capture<S, P>(self, name: S, p: P) -> Self
+    where
+        S: Into<String>,
+        P: Predicate<str> + Send + Sync + 'static,
+    {
+        self.add(CaptureGroup::Name(name.into()), crate::BoxPredicate::new(p))
+    }
+
+    /// Also require the numbered capture group of the first match to satisfy `p`.
+    ///
+    /// See [`RegexPredicate::capture_index`].
+    pub fn capture_index<P>(self, index: usize, p: P) -> Self
+    where
+        P: Predicate<str> + Send + Sync + 'static,
+    {
+        self.add(CaptureGroup::Index(index), crate::BoxPredicate::new(p))
+    }
+
+    fn add(mut self, group: CaptureGroup, p: crate::BoxPredicate<str>) -> Self {
+        let exists = match &group {
+            CaptureGroup::Name(name) => self.re.capture_names().flatten().any(|n| n == name),
+            CaptureGroup::Index(index) => *index < self.re.captures_len(),
+        };
+        assert!(
+            exists,
+            "regex `{}` has no capture group {group}",
+            self.re.as_str()
+        );
+        let label = format!("capture {group}");
+        self.captures.push(Capture {
+            group,
+            label,
+            p: sync::Arc::new(p),
+        });
+        self
+    }
+
+    fn captured<'s>(captures: &regex::Captures<'s>, group: &CaptureGroup) -> Option<&'s str> {
+        match group {
+            CaptureGroup::Name(name) => captures.name(name),
+            CaptureGroup::Index(index) => captures.get(*index),
+        }
+        .map(|m| m.as_str())
+    }
+}
+
+impl Predicate<str> for RegexCapturePredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.re
+            .captures(variable)
+            .map(|captures| {
+                self.captures.iter().all(|capture| {
+                    Self::captured(&captures, &capture.group)
+                        .map(|text| capture.p.eval(text))
+                        .unwrap_or(false)
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        let Some(captures) = self.re.captures(variable) else {
+            return (!expected).then(|| {
+                reflection::Case::new(Some(self), false)
+                    .add_product(reflection::Product::new("var", variable.to_owned()))
+            });
+        };
+
+        let result = self.eval(variable);
+        if result != expected {
+            return None;
+        }
+        let mut case = reflection::Case::new(Some(self), result);
+        for capture in &self.captures {
+            let text = Self::captured(&captures, &capture.group);
+            let value = text
+                .map(|text| format!("{text:?}"))
+                .unwrap_or_else(|| "<not captured>".to_owned());
+            case = case.add_product(reflection::Product::new(capture.label.clone(), value));
+            if let Some(child) = text.and_then(|text| capture.p.find_case(expected, text)) {
+                case = case.add_child(child);
+            }
+        }
+        Some(case)
+    }
+}
+
+impl reflection::PredicateReflection for RegexCapturePredicate {
+    fn children<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Child<'a>> + 'a> {
+        let params: Vec<_> = self
+            .captures
+            .iter()
+            .map(|capture| reflection::Child::new(&capture.label, &*capture.p))
+            .collect();
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for RegexCapturePredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("is_match"),
+            palette.expected(&self.re),
+        )?;
+        for capture in &self.captures {
+            write!(
+                f,
+                ".{}({})",
+                palette.description("capture"),
+                palette.expected(&capture.group),
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// Creates a new `Predicate` that uses a regular expression to match the string.
 ///
 /// # Examples
@@ -132,3 +366,218 @@ where
 {
     regex::Regex::new(pattern.as_ref()).map(|re| RegexPredicate { re })
 }
+
+/// Predicate that matches against several regular expressions at once.
+///
+/// This is created by the `predicate::str::matches_any` and `predicate::str::matches_all`.
+#[derive(Debug, Clone)]
+pub struct RegexSetPredicate {
+    set: regex::RegexSet,
+    all: bool,
+}
+
+impl RegexSetPredicate {
+    fn list(&self, matches: &regex::SetMatches, matched: bool) -> String {
+        let patterns: Vec<_> = self
+            .set
+            .patterns()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| matches.matched(*i) == matched)
+            .map(|(_, pattern)| format!("/{pattern}/"))
+            .collect();
+        patterns.join(", ")
+    }
+}
+
+impl Predicate<str> for RegexSetPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        if self.all {
+            self.set.matches(variable).matched_all()
+        } else {
+            self.set.is_match(variable)
+        }
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        let matches = self.set.matches(variable);
+        let result = if self.all {
+            matches.matched_all()
+        } else {
+            matches.matched_any()
+        };
+        if result != expected {
+            return None;
+        }
+
+        let mut case = reflection::Case::new(Some(self), result)
+            .add_product(reflection::Product::new("var", variable.to_owned()));
+        if matches.matched_any() {
+            case = case.add_product(reflection::Product::new(
+                "matched",
+                self.list(&matches, true),
+            ));
+        }
+        if !matches.matched_all() {
+            case = case.add_product(reflection::Product::new(
+                "unmatched",
+                self.list(&matches, false),
+            ));
+        }
+        Some(case)
+    }
+}
+
+impl reflection::PredicateReflection for RegexSetPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let params: Vec<_> = self
+            .set
+            .patterns()
+            .iter()
+            .map(|pattern| reflection::Parameter::new("pattern", pattern))
+            .collect();
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for RegexSetPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        let name = if self.all {
+            "matches_all"
+        } else {
+            "matches_any"
+        };
+        write!(
+            f,
+            "{}.{}({} patterns)",
+            palette.var("var"),
+            palette.description(name),
+            palette.expected(self.set.len()),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that checks if any of the regular expressions match the string.
+///
+/// All patterns are matched in a single pass over the string.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::matches_any(["^INFO", "^WARN"]).unwrap();
+/// assert_eq!(true, predicate_fn.eval("WARN disk almost full"));
+/// assert_eq!(false, predicate_fn.eval("ERROR disk full"));
+/// ```
+pub fn matches_any<I, S>(patterns: I) -> Result<RegexSetPredicate, RegexError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    regex::RegexSet::new(patterns).map(|set| RegexSetPredicate { set, all: false })
+}
+
+/// Creates a new `Predicate` that checks if all of the regular expressions match the string.
+///
+/// All patterns are matched in a single pass over the string.  With no patterns, every string
+/// passes, just as [`matches_any`] with no patterns fails every string.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::matches_all(["started", "finished"]).unwrap();
+/// assert_eq!(true, predicate_fn.eval("started\nworking\nfinished"));
+/// assert_eq!(false, predicate_fn.eval("started\nworking"));
+/// ```
+pub fn matches_all<I, S>(patterns: I) -> Result<RegexSetPredicate, RegexError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    regex::RegexSet::new(patterns).map(|set| RegexSetPredicate { set, all: true })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn capture_products() {
+        let p = predicate::str::is_match(r"(?<major>\d+)\.(?<minor>\d+)?")
+            .unwrap()
+            .capture("major", predicate::eq("1"))
+            .capture("minor", predicate::eq("2"));
+        assert!(!p.eval("1."));
+
+        let case = p.find_case(false, "1.").unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect();
+        assert_eq!(
+            products,
+            [
+                ("capture major".to_owned(), "\"1\"".to_owned()),
+                ("capture minor".to_owned(), "<not captured>".to_owned()),
+            ]
+        );
+        assert_eq!(case.children().count(), 0);
+
+        let case = p.find_case(false, "1.3").unwrap();
+        assert_eq!(case.children().count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no capture group prot")]
+    fn capture_unknown_name() {
+        let _ = predicate::str::is_match(r"port (?<port>\d+)")
+            .unwrap()
+            .capture("prot", predicate::always());
+    }
+
+    #[test]
+    #[should_panic(expected = "has no capture group 2")]
+    fn capture_unknown_index() {
+        let _ = predicate::str::is_match(r"v(\d+)")
+            .unwrap()
+            .capture_index(1, predicate::always())
+            .capture_index(2, predicate::always());
+    }
+
+    #[test]
+    fn set_reports_each_pattern() {
+        let p = predicate::str::matches_all(["a", "b", "c"]).unwrap();
+        let case = p.find_case(false, "ab").unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect();
+        assert_eq!(
+            products,
+            [
+                ("var".to_owned(), "ab".to_owned()),
+                ("matched".to_owned(), "/a/, /b/".to_owned()),
+                ("unmatched".to_owned(), "/c/".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_set() {
+        let patterns: [&str; 0] = [];
+        assert!(
+            predicate::str::matches_all(patterns)
+                .unwrap()
+                .eval("anything")
+        );
+        assert!(
+            !predicate::str::matches_any(patterns)
+                .unwrap()
+                .eval("anything")
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use crate::Predicate;
+use crate::distance;
+use crate::reflection;
+
+/// Predicate that ensures two strings are similar enough, by edit distance.
+///
+/// This is created by the `predicate::str::similar_to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarPredicate {
+    expected: String,
+    max_distance: Option<usize>,
+    min_ratio: Option<f64>,
+    damerau: bool,
+}
+
+impl SimilarPredicate {
+    /// Set the number of single-character edits allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::similar_to("kitten").max_distance(3);
+    /// assert_eq!(true, predicate_fn.eval("sitting"));
+    /// assert_eq!(false, predicate_fn.eval("mitten mittens"));
+    /// ```
+    pub fn max_distance(mut self, distance: usize) -> Self {
+        self.max_distance = Some(distance);
+        self
+    }
+
+    /// Set the lowest similarity allowed, from `0.0` (nothing in common) to `1.0` (identical).
+    ///
+    /// The ratio is `1 - distance / length`, where `length` is the character count of the longer
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::similar_to("connection refused").min_ratio(0.9);
+    /// assert_eq!(true, predicate_fn.eval("Connection refused"));
+    /// assert_eq!(false, predicate_fn.eval("connection reset"));
+    /// ```
+    pub fn min_ratio(mut self, ratio: f64) -> Self {
+        self.min_ratio = Some(ratio);
+        self
+    }
+
+    /// Count swapping two adjacent characters as a single edit (Damerau-Levenshtein distance).
+    ///
+    /// Defaults to `false`, where a swap is two edits (Levenshtein distance).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::similar_to("receive").max_distance(1);
+    /// assert_eq!(false, predicate_fn.eval("recieve"));
+    /// assert_eq!(true, predicate_fn.damerau(true).eval("recieve"));
+    /// ```
+    pub fn damerau(mut self, yes: bool) -> Self {
+        self.damerau = yes;
+        self
+    }
+
+    /// The edit distance and similarity ratio of `variable`.
+    fn measure(&self, variable: &str) -> (usize, f64) {
+        let expected: Vec<_> = self.expected.chars().collect();
+        let variable: Vec<_> = variable.chars().collect();
+        let distance = distance::distance(&expected, &variable, self.damerau);
+        let length = expected.len().max(variable.len());
+        let ratio = if length == 0 {
+            1.0
+        } else {
+            1.0 - distance as f64 / length as f64
+        };
+        (distance, ratio)
+    }
+
+    fn accepts(&self, distance: usize, ratio: f64) -> bool {
+        if self.max_distance.is_none() && self.min_ratio.is_none() {
+            return distance == 0;
+        }
+        self.max_distance.map(|max| distance <= max).unwrap_or(true)
+            && self.min_ratio.map(|min| min <= ratio).unwrap_or(true)
+    }
+}
+
+impl Predicate<str> for SimilarPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        let (distance, ratio) = self.measure(variable);
+        self.accepts(distance, ratio)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        let (distance, ratio) = self.measure(variable);
+        let actual = self.accepts(distance, ratio);
+        if expected == actual {
+            Some(
+                reflection::Case::new(Some(self), actual)
+                    .add_product(reflection::Product::new("var", variable.to_owned()))
+                    .add_product(reflection::Product::new("actual distance", distance))
+                    .add_product(reflection::Product::new("actual ratio", ratio)),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+impl reflection::PredicateReflection for SimilarPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let mut params = Vec::new();
+        if let Some(max_distance) = &self.max_distance {
+            params.push(reflection::Parameter::new("max distance", max_distance));
+        }
+        if let Some(min_ratio) = &self.min_ratio {
+            params.push(reflection::Parameter::new("min ratio", min_ratio));
+        }
+        params.push(reflection::Parameter::new("damerau", &self.damerau));
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for SimilarPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{}.{}({})",
+            palette.var("var"),
+            palette.description("similar_to"),
+            palette.expected(format_args!("{:?}", self.expected))
+        )
+    }
+}
+
+/// Creates a new `Predicate` that ensures a string is similar to `expected`, by edit distance.
+///
+/// Until [`SimilarPredicate::max_distance`] or [`SimilarPredicate::min_ratio`] is set, the strings
+/// must be identical.  When both are set, both must hold.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::similar_to("Hello World");
+/// assert_eq!(true, predicate_fn.eval("Hello World"));
+/// assert_eq!(false, predicate_fn.eval("Hello world"));
+/// assert_eq!(true, predicate_fn.max_distance(1).eval("Hello world"));
+/// ```
+pub fn similar_to<S>(expected: S) -> SimilarPredicate
+where
+    S: Into<String>,
+{
+    SimilarPredicate {
+        expected: expected.into(),
+        max_distance: None,
+        min_ratio: None,
+        damerau: false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn products(case: &reflection::Case<'_>) -> Vec<(String, String)> {
+        case.products()
+            .map(|product| (product.name().to_owned(), product.value().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_distance_and_ratio() {
+        let p = similar_to("kitten").max_distance(2);
+        let case = p.find_case(false, "sitting").unwrap();
+        assert_eq!(
+            products(&case),
+            [
+                ("var".to_owned(), "sitting".to_owned()),
+                ("actual distance".to_owned(), "3".to_owned()),
+                ("actual ratio".to_owned(), (1.0 - 3.0 / 7.0).to_string()),
+            ]
+        );
+        assert!(p.find_case(true, "sitting").is_none());
+    }
+
+    #[test]
+    fn empty_strings_are_identical() {
+        let p = similar_to("").min_ratio(1.0);
+        let case = p.find_case(true, "").unwrap();
+        assert_eq!(
+            products(&case)[1..],
+            [
+                ("actual distance".to_owned(), "0".to_owned()),
+                ("actual ratio".to_owned(), "1".to_owned()),
+            ]
+        );
+        let case = p.find_case(false, "a").unwrap();
+        assert_eq!(
+            products(&case)[2],
+            ("actual ratio".to_owned(), "0".to_owned())
+        );
+    }
+
+    #[test]
+    fn damerau_counts_swaps_once() {
+        let p = similar_to("abcd");
+        let case = p.find_case(false, "acbd").unwrap();
+        assert_eq!(
+            products(&case)[1],
+            ("actual distance".to_owned(), "2".to_owned())
+        );
+        let p = p.damerau(true);
+        let case = p.find_case(false, "acbd").unwrap();
+        assert_eq!(
+            products(&case)[1],
+            ("actual distance".to_owned(), "1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow;
+use std::fmt;
+
+use crate::Predicate;
+use crate::reflection;
+use crate::str::difference::DiffOptions;
+
+const INLINE_WILDCARD: &str = "[..]";
+const LINE_WILDCARD: &str = "...";
+
+/// A single line of the expected output.
+enum Line<'a> {
+    /// `...`: any number of lines, including none.
+    Any,
+    /// Literal text, separated wherever `[..]` appeared.
+    Pattern(&'a str, Vec<String>),
+}
+
+impl Line<'_> {
+    fn matches(&self, actual: &str) -> bool {
+        match self {
+            Line::Any => true,
+            Line::Pattern(_, chunks) => matches_chunks(chunks, actual),
+        }
+    }
+}
+
+/// Match `actual` against literal `chunks` with a wildcard between each pair.
+fn matches_chunks(chunks: &[String], actual: &str) -> bool {
+    let (first, rest) = chunks.split_first().expect("split always yields a chunk");
+    let Some(mut remaining) = actual.strip_prefix(first.as_str()) else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return remaining.is_empty();
+    };
+    for chunk in middle {
+        match remaining.find(chunk.as_str()) {
+            Some(i) => remaining = &remaining[i + chunk.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last.as_str())
+}
+
+/// Predicate that diffs two strings, allowing for wildcards in the expected string.
+///
+/// This is created by the `predicate::str::matches_snapshot`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotPredicate {
+    orig: borrow::Cow<'static, str>,
+    redactions: Vec<(borrow::Cow<'static, str>, borrow::Cow<'static, str>)>,
+}
+
+impl SnapshotPredicate {
+    /// Allow `placeholder` in the expected string to stand in for `value`.
+    ///
+    /// This is useful for values that change between runs or machines, like temporary
+    /// directories.  `[EXE]` is pre-defined as [`std::env::consts::EXE_SUFFIX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use predicates::prelude::*;
+    ///
+    /// let predicate_fn = predicate::str::matches_snapshot("Created [ROOT]/foo[EXE]")
+    ///     .redact("[ROOT]", "/tmp/abc123");
+    /// let actual = format!("Created /tmp/abc123/foo{}", std::env::consts::EXE_SUFFIX);
+    /// assert_eq!(true, predicate_fn.eval(&actual));
+    /// assert_eq!(false, predicate_fn.eval("Created /tmp/xyz/foo"));
+    /// ```
+    pub fn redact<K, V>(mut self, placeholder: K, value: V) -> Self
+    where
+        K: Into<borrow::Cow<'static, str>>,
+        V: Into<borrow::Cow<'static, str>>,
+    {
+        let placeholder = placeholder.into();
+        let value = value.into();
+        match self.redactions.iter_mut().find(|(p, _)| *p == placeholder) {
+            Some(redaction) => redaction.1 = value,
+            None => self.redactions.push((placeholder, value)),
+        }
+        self
+    }
+
+    fn lines(&self) -> Vec<Line<'_>> {
+        self.orig
+            .lines()
+            .map(|line| {
+                if line == LINE_WILDCARD {
+                    return Line::Any;
+                }
+                let chunks = line
+                    .split(INLINE_WILDCARD)
+                    .map(|chunk| {
+                        self.redactions.iter().fold(
+                            chunk.to_owned(),
+                            |chunk, (placeholder, value)| {
+                                chunk.replace(placeholder.as_ref(), value)
+                            },
+                        )
+                    })
+                    .collect();
+                Line::Pattern(line, chunks)
+            })
+            .collect()
+    }
+
+    fn matches(&self, variable: &str) -> bool {
+        let expected = self.lines();
+        let actual: Vec<_> = variable.lines().collect();
+
+        // `matched[i][j]`: whether `expected[i..]` matches `actual[j..]`
+        let mut matched = vec![vec![false; actual.len() + 1]; expected.len() + 1];
+        matched[expected.len()][actual.len()] = true;
+        for (i, line) in expected.iter().enumerate().rev() {
+            for j in (0..=actual.len()).rev() {
+                matched[i][j] = match line {
+                    Line::Any => matched[i + 1][j] || (j < actual.len() && matched[i][j + 1]),
+                    Line::Pattern(..) => {
+                        j < actual.len() && matched[i + 1][j + 1] && line.matches(actual[j])
+                    }
+                };
+            }
+        }
+        matched[0][0]
+    }
+
+    /// Rewrite `variable` so that lines satisfied by a wildcard read the same as the expected
+    /// string, leaving only the real differences for the diff.
+    fn align(&self, variable: &str) -> String {
+        let expected = self.lines();
+        let actual: Vec<_> = variable.lines().collect();
+
+        let mut normalized = Vec::new();
+        let mut j = 0;
+        for (i, line) in expected.iter().enumerate() {
+            match line {
+                Line::Any => {
+                    let next = expected[i + 1..]
+                        .iter()
+                        .find(|line| !matches!(line, Line::Any));
+                    j = match next {
+                        Some(next) => actual[j..]
+                            .iter()
+                            .position(|a| next.matches(a))
+                            .map(|offset| j + offset)
+                            .unwrap_or(j),
+                        None => actual.len(),
+                    };
+                    normalized.push(LINE_WILDCARD);
+                }
+                Line::Pattern(orig, _) => {
+                    if let Some(offset) = actual[j..].iter().position(|a| line.matches(a)) {
+                        normalized.extend_from_slice(&actual[j..j + offset]);
+                        normalized.push(orig);
+                        j += offset + 1;
+                    } else if j < actual.len() {
+                        normalized.push(actual[j]);
+                        j += 1;
+                    }
+                }
+            }
+        }
+        normalized.extend_from_slice(&actual[j..]);
+
+        let mut normalized = normalized.join("\n");
+        normalized.push('\n');
+        normalized
+    }
+}
+
+impl Predicate<str> for SnapshotPredicate {
+    fn eval(&self, variable: &str) -> bool {
+        self.matches(variable)
+    }
+
+    fn find_case<'a>(&'a self, expected: bool, variable: &str) -> Option<reflection::Case<'a>> {
+        let result = self.matches(variable);
+        if result != expected {
+            None
+        } else if result {
+            Some(reflection::Case::new(Some(self), result))
+        } else {
+            let diff = DiffOptions::default().render(&self.orig, &self.align(variable));
+            Some(
+                reflection::Case::new(Some(self), result)
+                    .add_product(reflection::Product::new("diff", diff)),
+            )
+        }
+    }
+}
+
+impl reflection::PredicateReflection for SnapshotPredicate {
+    fn parameters<'a>(&'a self) -> Box<dyn Iterator<Item = reflection::Parameter<'a>> + 'a> {
+        let mut params = vec![reflection::Parameter::new("original", &self.orig)];
+        params.extend(
+            self.redactions
+                .iter()
+                .map(|(placeholder, value)| reflection::Parameter::new(placeholder, value)),
+        );
+        Box::new(params.into_iter())
+    }
+}
+
+impl fmt::Display for SnapshotPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let palette = crate::Palette::new(f.alternate());
+        write!(
+            f,
+            "{:#} {:#} {:#}",
+            palette.description("matches_snapshot"),
+            palette.expected("original"),
+            palette.var("var"),
+        )
+    }
+}
+
+/// Creates a new `Predicate` that diffs two strings, allowing for wildcards in the expected
+/// string.
+///
+/// The expected string supports:
+/// - `[..]`: any text within a single line
+/// - `...` on a line of its own: any number of lines, including none
+/// - Placeholders registered with [`SnapshotPredicate::redact`], like `[EXE]`
+///
+/// Lines are compared without their line endings.  On failure, lines satisfied by a wildcard are
+/// left out of the reported diff.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::str::matches_snapshot(
+///     "\
+/// Compiling foo v[..]
+/// ...
+/// Finished in [..]s
+/// ",
+/// );
+/// assert_eq!(true, predicate_fn.eval("Compiling foo v1.2.3\nFinished in 0.5s\n"));
+/// assert_eq!(true, predicate_fn.eval("Compiling foo v1.2.3\nCompiling bar v0.1.0\nFinished in 0.5s\n"));
+/// assert_eq!(false, predicate_fn.eval("Compiling foo v1.2.3\nFailed\n"));
+/// ```
+pub fn matches_snapshot<S>(orig: S) -> SnapshotPredicate
+where
+    S: Into<borrow::Cow<'static, str>>,
+{
+    SnapshotPredicate {
+        orig: orig.into(),
+        redactions: Vec::new(),
+    }
+    .redact("[EXE]", std::env::consts::EXE_SUFFIX)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn inline_wildcards() {
+        let p = predicate::str::matches_snapshot("a[..]c[..]e");
+        assert!(p.eval("abcde"));
+        assert!(p.eval("ace"));
+        assert!(!p.eval("abde"));
+        assert!(!p.eval("abcdef"));
+        assert!(!p.eval("ab\nce"));
+    }
+
+    #[test]
+    fn line_wildcards() {
+        let p = predicate::str::matches_snapshot("a\n...\nz\n...");
+        assert!(p.eval("a\nz"));
+        assert!(p.eval("a\nb\nc\nz\ny"));
+        assert!(!p.eval("a\nb"));
+    }
+
+    #[test]
+    fn align_skips_wildcards() {
+        let p = super::matches_snapshot("one [..]\n...\nthree\nfour [..]");
+        assert_eq!(
+            p.align("one 1\nextra\nthree\nfive 5\nfour 4"),
+            "one [..]\n...\nthree\nfive 5\nfour [..]\n"
+        );
+    }
+}
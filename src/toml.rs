@@ -0,0 +1,117 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! TOML Predicates
+//!
+//! This module contains predicates that compare TOML documents by structure, rather than by
+//! text.  Documents are compared as JSON value trees, like the [`json`][crate::json] predicates,
+//! with datetimes compared as strings.  Differences are reported by dotted key path.
+
+use serde_json::Value;
+
+use crate::Predicate;
+use crate::json::format::{self, Format};
+pub use crate::json::{EqPredicate, PointerPredicate, SubsetPredicate};
+
+/// Creates a new `Predicate` that ensures a TOML document is structurally equal to `expected`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let expected: toml::Table = "[server]\nport = 8080".parse().unwrap();
+/// let predicate_fn = predicate::toml::eq(expected);
+/// assert_eq!(true, predicate_fn.eval("server = { port = 8080 }"));
+/// assert_eq!(false, predicate_fn.eval("server = { port = 8081 }"));
+/// ```
+pub fn eq<V>(expected: V) -> EqPredicate
+where
+    V: Into<::toml::Value>,
+{
+    EqPredicate {
+        expected: format::from_toml(expected.into()),
+        format: Format::Toml,
+    }
+}
+
+/// Creates a new `Predicate` that ensures `expected` is a subset of a TOML document.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let expected: toml::Table = "[package]\nname = \"foo\"".parse().unwrap();
+/// let predicate_fn = predicate::toml::is_subset_of(expected);
+/// assert_eq!(true, predicate_fn.eval("[package]\nname = \"foo\"\nversion = \"1.0.0\""));
+/// assert_eq!(false, predicate_fn.eval("[package]\nname = \"bar\""));
+/// ```
+pub fn is_subset_of<V>(expected: V) -> SubsetPredicate
+where
+    V: Into<::toml::Value>,
+{
+    SubsetPredicate {
+        expected: format::from_toml(expected.into()),
+        format: Format::Toml,
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the value at `path` within a TOML document.
+///
+/// `path` is a dotted key path, like `"server.ports.0"`, or a JSON pointer for keys that contain
+/// a dot.  The predicate fails when there is no value at `path`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::toml::path("server.ports.0", predicate::toml::eq(80));
+/// assert_eq!(true, predicate_fn.eval("[server]\nports = [80, 443]"));
+/// assert_eq!(false, predicate_fn.eval("[server]\nports = [8080]"));
+/// ```
+pub fn path<S, P>(path: S, p: P) -> PointerPredicate<P>
+where
+    S: Into<String>,
+    P: Predicate<Value>,
+{
+    let path = path.into();
+    PointerPredicate {
+        pointer: format::pointer_from_path(&path),
+        path,
+        p,
+        format: Format::Toml,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn differences_use_key_paths() {
+        let expected: ::toml::Table = "[server]\nhost = \"a\"\n\"x.y\" = 1".parse().unwrap();
+        let p = eq(expected);
+        let case = p.find_case(false, "[server]\nhost = \"b\"").unwrap();
+        let names: Vec<_> = case
+            .products()
+            .map(|product| product.name().to_owned())
+            .collect();
+        assert_eq!(names, ["server.host", "server.\"x.y\""]);
+    }
+
+    #[test]
+    fn parse_error_has_position() {
+        let p = eq(::toml::Table::new());
+        let case = p.find_case(false, "a = 1\nb = ").unwrap();
+        let error = case.products().next().unwrap();
+        assert_eq!(error.name(), "error");
+        assert!(error.value().to_string().contains("line 2, column"));
+    }
+}
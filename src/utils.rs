@@ -46,6 +46,37 @@ where
     }
 }
 
+/// Display bytes as an escaped byte string, eliding the middle of long content.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct BytesAdapter(pub(crate) Vec<u8>);
+
+impl BytesAdapter {
+    const MAX_SHOWN: usize = 64;
+}
+
+impl fmt::Display for BytesAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.len() <= Self::MAX_SHOWN {
+            write!(f, "b\"{}\"", self.0.escape_ascii())
+        } else {
+            let half = Self::MAX_SHOWN / 2;
+            write!(
+                f,
+                "b\"{}\"...b\"{}\" ({} bytes)",
+                self.0[..half].escape_ascii(),
+                self.0[self.0.len() - half..].escape_ascii(),
+                self.0.len()
+            )
+        }
+    }
+}
+
+impl fmt::Debug for BytesAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 pub(crate) fn default_find_case<'a, P, Item>(
     pred: &'a P,
     expected: bool,
@@ -0,0 +1,119 @@
+// Copyright (c) 2018 The predicates-rs Project Developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! YAML Predicates
+//!
+//! This module contains predicates that compare YAML documents by structure, rather than by
+//! text.  Documents are compared as JSON value trees, like the [`json`][crate::json] predicates,
+//! with tags dropped and non-string keys written as YAML.  Differences are reported by dotted key
+//! path.
+
+use serde_json::Value;
+
+use crate::Predicate;
+use crate::json::format::{self, Format};
+pub use crate::json::{EqPredicate, PointerPredicate, SubsetPredicate};
+
+/// Creates a new `Predicate` that ensures a YAML document is structurally equal to `expected`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let expected: serde_norway::Value = serde_norway::from_str("name: foo\ntags: [a, b]").unwrap();
+/// let predicate_fn = predicate::yaml::eq(expected);
+/// assert_eq!(true, predicate_fn.eval("tags:\n  - a\n  - b\nname: foo\n"));
+/// assert_eq!(false, predicate_fn.eval("name: foo\ntags: [b, a]"));
+/// ```
+pub fn eq<V>(expected: V) -> EqPredicate
+where
+    V: Into<serde_norway::Value>,
+{
+    EqPredicate {
+        expected: format::from_yaml(expected.into()),
+        format: Format::Yaml,
+    }
+}
+
+/// Creates a new `Predicate` that ensures `expected` is a subset of a YAML document.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let expected: serde_norway::Value = serde_norway::from_str("status: ok").unwrap();
+/// let predicate_fn = predicate::yaml::is_subset_of(expected);
+/// assert_eq!(true, predicate_fn.eval("status: ok\nelapsed: 12"));
+/// assert_eq!(false, predicate_fn.eval("status: failed"));
+/// ```
+pub fn is_subset_of<V>(expected: V) -> SubsetPredicate
+where
+    V: Into<serde_norway::Value>,
+{
+    SubsetPredicate {
+        expected: format::from_yaml(expected.into()),
+        format: Format::Yaml,
+    }
+}
+
+/// Creates a new `Predicate` that applies `p` to the value at `path` within a YAML document.
+///
+/// `path` is a dotted key path, like `"spec.ports.0"`, or a JSON pointer for keys that contain
+/// a dot.  The predicate fails when there is no value at `path`.
+///
+/// # Examples
+///
+/// ```
+/// use predicates::prelude::*;
+///
+/// let predicate_fn = predicate::yaml::path("spec.replicas", predicate::yaml::eq(3));
+/// assert_eq!(true, predicate_fn.eval("spec:\n  replicas: 3"));
+/// assert_eq!(false, predicate_fn.eval("spec: {}"));
+/// ```
+pub fn path<S, P>(path: S, p: P) -> PointerPredicate<P>
+where
+    S: Into<String>,
+    P: Predicate<Value>,
+{
+    let path = path.into();
+    PointerPredicate {
+        pointer: format::pointer_from_path(&path),
+        path,
+        p,
+        format: Format::Yaml,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn differences_use_key_paths() {
+        let expected: serde_norway::Value =
+            serde_norway::from_str("a:\n  b: [1, 2]\n1: x").unwrap();
+        let p = eq(expected);
+        let case = p.find_case(false, "a:\n  b: [1, 3]\n1: x\n").unwrap();
+        let products: Vec<_> = case
+            .products()
+            .map(|product| format!("{}: {}", product.name(), product.value()))
+            .collect();
+        assert_eq!(products, ["a.b.1: expected 2, found 3"]);
+    }
+
+    #[test]
+    fn parse_error_has_position() {
+        let p = eq(serde_norway::Value::Null);
+        let case = p.find_case(false, "a: 1\nb: [").unwrap();
+        let error = case.products().next().unwrap();
+        assert_eq!(error.name(), "error");
+        assert!(error.value().to_string().contains("at line 3 column 1"));
+    }
+}